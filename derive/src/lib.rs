@@ -0,0 +1,292 @@
+//! Derive macros for [`equivalent-flipped`](https://docs.rs/equivalent-flipped).
+//!
+//! Use them through the `derive` feature of `equivalent-flipped`, which
+//! re-exports them next to the traits they implement.
+
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{
+  parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Ident, Index, LitInt, LitStr,
+  Member, Result, Type,
+};
+
+/// Derives `Equivalent<Query>` for a struct, comparing its fields with the
+/// fields of the same name in the query type.
+///
+/// # Container attributes
+///
+/// - `#[equivalent(query = Type)]`: the query type to implement `Equivalent`
+///   against, e.g. `#[equivalent(query = FooRef<'_>)]`. Repeat the attribute to
+///   implement against several query types. Required.
+/// - `#[equivalent(hash)]`: also implement `EquivalentHash<Query>`, feeding the
+///   hasher each compared field in declaration order. The query type must hash
+///   the same fields in the same order for the hashing contract to hold.
+///
+/// # Field attributes
+///
+/// - `#[equivalent(rename = name)]`: compare with the query field `name`
+///   instead of the field of the same name (or position, for tuple structs).
+/// - `#[equivalent(deref)]`: the query field is a reference (or smart pointer),
+///   so compare with what it points to, e.g. a `String` field against a
+///   `&'a str` query field.
+/// - `#[equivalent(skip)]`: do not compare (or hash) this field.
+///
+/// # Skipped fields and `Hash`
+///
+/// `hash_like` leaves skipped fields out, but `#[derive(Hash)]` on the struct
+/// hashes every field, so the two disagree as soon as a field is skipped. Hash
+/// maps hash their stored keys through `Hash`, so a struct with skipped fields
+/// which is used as a hash map key needs a hand-written `Hash` implementation
+/// that leaves them out as well, or its entries will not be found by queries.
+///
+/// # Generics
+///
+/// The struct may have lifetime and const parameters, but not type parameters:
+/// the bounds they would need depend on the types of the query fields, which
+/// the derive cannot see. Implement the traits by hand for generic structs.
+#[proc_macro_derive(Equivalent, attributes(equivalent))]
+pub fn derive_equivalent(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand_equivalent(&input)
+    .unwrap_or_else(compile_error)
+    .into()
+}
+
+/// Derives `Comparable<Query>` for a struct, comparing its fields with the
+/// fields of the query type lexicographically, in declaration order.
+///
+/// It accepts the same `#[equivalent(..)]` attributes as the `Equivalent`
+/// derive, which must be derived (or implemented) as well.
+#[proc_macro_derive(Comparable, attributes(equivalent))]
+pub fn derive_comparable(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand_comparable(&input)
+    .unwrap_or_else(compile_error)
+    .into()
+}
+
+/// Like `Error::into_compile_error`, but without the `::core` path, which does
+/// not resolve at the span of the error in edition 2015 crates.
+fn compile_error(error: Error) -> TokenStream2 {
+  error
+    .into_iter()
+    .map(|e| {
+      let message = e.to_string();
+      quote_spanned!(e.span()=> compile_error! { #message })
+    })
+    .collect()
+}
+
+struct Container {
+  queries: Vec<Type>,
+  hash: bool,
+}
+
+struct Field {
+  member: Member,
+  query: Member,
+  deref: bool,
+}
+
+impl Field {
+  /// The expression of the query field to compare this field with, given the
+  /// query is bound to `key`.
+  fn query_expr(&self) -> TokenStream2 {
+    let query = &self.query;
+    if self.deref {
+      quote!(&*key.#query)
+    } else {
+      quote!(&key.#query)
+    }
+  }
+}
+
+fn parse_container(input: &DeriveInput) -> Result<Container> {
+  if let Some(param) = input.generics.type_params().next() {
+    return Err(Error::new(
+      param.ident.span(),
+      "`Equivalent` and `Comparable` cannot be derived for structs with type parameters, \
+       implement them by hand instead",
+    ));
+  }
+
+  let mut container = Container {
+    queries: Vec::new(),
+    hash: false,
+  };
+
+  for attr in input
+    .attrs
+    .iter()
+    .filter(|a| a.path().is_ident("equivalent"))
+  {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("query") {
+        let value = meta.value()?;
+        let ty = if value.peek(LitStr) {
+          value.parse::<LitStr>()?.parse()?
+        } else {
+          value.parse()?
+        };
+        container.queries.push(ty);
+        Ok(())
+      } else if meta.path.is_ident("hash") {
+        container.hash = true;
+        Ok(())
+      } else {
+        Err(meta.error("unsupported container attribute, expected `query` or `hash`"))
+      }
+    })?;
+  }
+
+  if container.queries.is_empty() {
+    return Err(Error::new(
+      input.ident.span(),
+      "missing `#[equivalent(query = Type)]` attribute",
+    ));
+  }
+
+  Ok(container)
+}
+
+fn parse_fields(input: &DeriveInput) -> Result<Vec<Field>> {
+  let fields = match &input.data {
+    Data::Struct(data) => &data.fields,
+    _ => {
+      return Err(Error::new(
+        input.ident.span(),
+        "`Equivalent` and `Comparable` can only be derived for structs",
+      ))
+    }
+  };
+
+  let mut out = Vec::with_capacity(fields.len());
+  for (i, field) in fields.iter().enumerate() {
+    let member = match &field.ident {
+      Some(ident) => Member::Named(ident.clone()),
+      None => Member::Unnamed(Index::from(i)),
+    };
+    let attrs = field
+      .attrs
+      .iter()
+      .filter(|a| a.path().is_ident("equivalent"));
+    let mut skip = false;
+    let mut field = Field {
+      query: member.clone(),
+      member,
+      deref: false,
+    };
+
+    for attr in attrs {
+      attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("rename") {
+          let value = meta.value()?;
+          field.query = if value.peek(LitStr) {
+            value.parse::<LitStr>()?.parse()?
+          } else if value.peek(LitInt) {
+            Member::Unnamed(
+              value
+                .parse::<LitInt>()?
+                .base10_parse::<u32>()
+                .map(|i| Index {
+                  index: i,
+                  span: meta.path.span(),
+                })?,
+            )
+          } else {
+            Member::Named(value.parse::<Ident>()?)
+          };
+          Ok(())
+        } else if meta.path.is_ident("deref") {
+          field.deref = true;
+          Ok(())
+        } else if meta.path.is_ident("skip") {
+          skip = true;
+          Ok(())
+        } else {
+          Err(meta.error("unsupported field attribute, expected `rename`, `deref` or `skip`"))
+        }
+      })?;
+    }
+
+    if !skip {
+      out.push(field);
+    }
+  }
+
+  Ok(out)
+}
+
+fn expand_equivalent(input: &DeriveInput) -> Result<TokenStream2> {
+  let container = parse_container(input)?;
+  let fields = parse_fields(input)?;
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  let members: Vec<_> = fields.iter().map(|f| &f.member).collect();
+  let query_exprs: Vec<_> = fields.iter().map(Field::query_expr).collect();
+
+  let mut out = TokenStream2::new();
+  for query in &container.queries {
+    out.extend(quote! {
+      impl #impl_generics ::equivalent_flipped::Equivalent<#query> for #ident #ty_generics #where_clause {
+        #[inline]
+        fn equivalent(&self, key: &#query) -> bool {
+          true #(&& ::equivalent_flipped::Equivalent::equivalent(&self.#members, #query_exprs))*
+        }
+      }
+    });
+
+    if container.hash {
+      out.extend(quote! {
+        impl #impl_generics ::equivalent_flipped::EquivalentHash<#query> for #ident #ty_generics #where_clause {
+          #[inline]
+          fn hash_like<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+            #(
+              ::equivalent_flipped::__private::hash_like(
+                &self.#members,
+                |key: &#query| #query_exprs,
+                state,
+              );
+            )*
+          }
+        }
+      });
+    }
+  }
+
+  Ok(out)
+}
+
+fn expand_comparable(input: &DeriveInput) -> Result<TokenStream2> {
+  let container = parse_container(input)?;
+  let fields = parse_fields(input)?;
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  let members: Vec<_> = fields.iter().map(|f| &f.member).collect();
+  let query_exprs: Vec<_> = fields.iter().map(Field::query_expr).collect();
+
+  let mut out = TokenStream2::new();
+  for query in &container.queries {
+    out.extend(quote! {
+      impl #impl_generics ::equivalent_flipped::Comparable<#query> for #ident #ty_generics #where_clause {
+        #[inline]
+        fn compare(&self, key: &#query) -> ::core::cmp::Ordering {
+          #(
+            match ::equivalent_flipped::Comparable::compare(&self.#members, #query_exprs) {
+              ::core::cmp::Ordering::Equal => {}
+              ord => return ord,
+            }
+          )*
+          ::core::cmp::Ordering::Equal
+        }
+      }
+    });
+  }
+
+  Ok(out)
+}
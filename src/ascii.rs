@@ -0,0 +1,146 @@
+use core::{
+  borrow::Borrow,
+  cmp::Ordering,
+  hash::{Hash, Hasher},
+};
+
+transparent_wrapper! {
+  /// A key or query wrapper which compares, orders and hashes strings and byte
+  /// strings ignoring ASCII case.
+  ///
+  /// Store keys as `AsciiCaseInsensitive<String>` (or any `T: AsRef<str>`) and
+  /// look them up with `AsciiCaseInsensitive<str>`, or use `AsRef<[u8]>` types
+  /// and `AsciiCaseInsensitive<[u8]>` for byte strings. The owned forms
+  /// `Borrow` the unsized forms, so the blanket
+  /// [`Equivalent`](crate::Equivalent), [`Comparable`](crate::Comparable) and
+  /// [`EquivalentHash`](crate::EquivalentHash) implementations apply, and the
+  /// wrapper can be used in both ordered and hash maps.
+  ///
+  /// Plain `String` or `[u8]` keys are deliberately not comparable with this
+  /// wrapper: their own ordering and hashing do not fold case, so a map of them
+  /// cannot be searched case-insensitively.
+  #[derive(Debug, Default, Clone, Copy)]
+  pub struct AsciiCaseInsensitive<T: ?Sized>(pub T);
+}
+
+impl<T: ?Sized + AsRef<[u8]>> PartialEq for AsciiCaseInsensitive<T> {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.0.as_ref().eq_ignore_ascii_case(other.0.as_ref())
+  }
+}
+
+impl<T: ?Sized + AsRef<[u8]>> Eq for AsciiCaseInsensitive<T> {}
+
+impl<T: ?Sized + AsRef<[u8]>> PartialOrd for AsciiCaseInsensitive<T> {
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T: ?Sized + AsRef<[u8]>> Ord for AsciiCaseInsensitive<T> {
+  #[inline]
+  fn cmp(&self, other: &Self) -> Ordering {
+    let this = self.0.as_ref().iter().map(u8::to_ascii_lowercase);
+    let other = other.0.as_ref().iter().map(u8::to_ascii_lowercase);
+    this.cmp(other)
+  }
+}
+
+impl<T: ?Sized + AsRef<[u8]>> Hash for AsciiCaseInsensitive<T> {
+  #[inline]
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    for b in self.0.as_ref() {
+      state.write_u8(b.to_ascii_lowercase());
+    }
+    // Same terminator as `str`, so that `("ab", "c")` and `("a", "bc")` hash differently.
+    state.write_u8(0xff);
+  }
+}
+
+impl<T: AsRef<str>> Borrow<AsciiCaseInsensitive<str>> for AsciiCaseInsensitive<T> {
+  #[inline]
+  fn borrow(&self) -> &AsciiCaseInsensitive<str> {
+    AsciiCaseInsensitive::from_ref(self.0.as_ref())
+  }
+}
+
+impl<T: AsRef<[u8]>> Borrow<AsciiCaseInsensitive<[u8]>> for AsciiCaseInsensitive<T> {
+  #[inline]
+  fn borrow(&self) -> &AsciiCaseInsensitive<[u8]> {
+    AsciiCaseInsensitive::from_ref(self.0.as_ref())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::cmp::Ordering;
+  use std::{string::String, vec::Vec};
+
+  use super::AsciiCaseInsensitive;
+  use crate::{
+    tests::{hash, hash_like},
+    Comparable, Equivalent,
+  };
+
+  #[test]
+  fn eq_and_ord_fold_case() {
+    assert_eq!(
+      AsciiCaseInsensitive::from_ref("HeLLo"),
+      AsciiCaseInsensitive::from_ref("hello")
+    );
+    assert_ne!(
+      AsciiCaseInsensitive::from_ref("hello"),
+      AsciiCaseInsensitive::from_ref("hell")
+    );
+    // Plain ordering puts "Banana" first.
+    assert!(AsciiCaseInsensitive("apple") < AsciiCaseInsensitive("Banana"));
+    assert_eq!(
+      AsciiCaseInsensitive("ab").cmp(&AsciiCaseInsensitive("AB")),
+      Ordering::Equal
+    );
+    assert!(AsciiCaseInsensitive("A") < AsciiCaseInsensitive("ab"));
+  }
+
+  #[test]
+  fn non_ascii_is_not_folded() {
+    assert_ne!(
+      AsciiCaseInsensitive::from_ref("\u{c9}"),
+      AsciiCaseInsensitive::from_ref("\u{e9}")
+    );
+  }
+
+  #[test]
+  fn owned_keys_and_unsized_queries() {
+    let key = AsciiCaseInsensitive(String::from("Content-Type"));
+    let query = AsciiCaseInsensitive::from_ref("content-type");
+    assert!(key.equivalent(query));
+    assert_eq!(key.compare(query), Ordering::Equal);
+    assert_eq!(
+      key.compare(AsciiCaseInsensitive::from_ref("content-length")),
+      Ordering::Greater
+    );
+    assert_eq!(hash_like::<_, AsciiCaseInsensitive<str>>(&key), hash(query));
+
+    let key = AsciiCaseInsensitive(Vec::from(&b"GET"[..]));
+    let query = AsciiCaseInsensitive::from_ref(&b"get"[..]);
+    assert!(key.equivalent(query));
+    assert_eq!(
+      hash_like::<_, AsciiCaseInsensitive<[u8]>>(&key),
+      hash(query)
+    );
+  }
+
+  #[test]
+  fn hash_folds_case_and_terminates() {
+    assert_eq!(
+      hash(AsciiCaseInsensitive::from_ref("HeLLo")),
+      hash(AsciiCaseInsensitive::from_ref("hello"))
+    );
+    assert_ne!(
+      hash(&(AsciiCaseInsensitive("ab"), AsciiCaseInsensitive("c"))),
+      hash(&(AsciiCaseInsensitive("a"), AsciiCaseInsensitive("bc")))
+    );
+  }
+}
@@ -0,0 +1,177 @@
+use core::{cmp::Ordering, fmt};
+
+use super::{Comparable, Equivalent};
+
+/// A comparison between keys and queries carried as a value, so it can be
+/// chosen at runtime, e.g. a collation picked by a database.
+///
+/// This trait is object safe, so containers can store a
+/// `Box<dyn Comparator<K, Q>>` (or a `&dyn Comparator<K, Q>`). It is
+/// implemented for closures `Fn(&K, &Q) -> Ordering` and, through
+/// [`ByComparable`], for the static [`Comparable`] implementations. Queries can
+/// be bridged back to [`Comparable`] with [`WithComparator`].
+pub trait Comparator<K: ?Sized, Q: ?Sized> {
+  /// Compare `key` to `query` and return their ordering.
+  fn compare(&self, key: &K, query: &Q) -> Ordering;
+
+  /// Returns `true` if `key` and `query` are equal.
+  #[inline]
+  fn equivalent(&self, key: &K, query: &Q) -> bool {
+    self.compare(key, query) == Ordering::Equal
+  }
+}
+
+impl<K, Q, F> Comparator<K, Q> for F
+where
+  K: ?Sized,
+  Q: ?Sized,
+  F: Fn(&K, &Q) -> Ordering,
+{
+  #[inline]
+  fn compare(&self, key: &K, query: &Q) -> Ordering {
+    self(key, query)
+  }
+}
+
+/// A [`Comparator`] which uses the [`Comparable`] implementation of the key.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ByComparable;
+
+impl<K, Q> Comparator<K, Q> for ByComparable
+where
+  K: ?Sized + Comparable<Q>,
+  Q: ?Sized,
+{
+  #[inline]
+  fn compare(&self, key: &K, query: &Q) -> Ordering {
+    key.compare(query)
+  }
+
+  #[inline]
+  fn equivalent(&self, key: &K, query: &Q) -> bool {
+    key.equivalent(query)
+  }
+}
+
+/// A query which is compared to keys by a [`Comparator`], so containers built
+/// on [`Comparable`] can be searched with a comparator chosen at runtime.
+pub struct WithComparator<'a, C: ?Sized, Q: ?Sized> {
+  comparator: &'a C,
+  query: &'a Q,
+}
+
+impl<'a, C: ?Sized, Q: ?Sized> WithComparator<'a, C, Q> {
+  /// Creates a query comparing keys to `query` with `comparator`.
+  #[inline]
+  pub fn new(comparator: &'a C, query: &'a Q) -> Self {
+    Self { comparator, query }
+  }
+
+  /// Returns the comparator.
+  #[inline]
+  pub fn comparator(&self) -> &'a C {
+    self.comparator
+  }
+
+  /// Returns the query.
+  #[inline]
+  pub fn query(&self) -> &'a Q {
+    self.query
+  }
+}
+
+impl<C: ?Sized, Q: ?Sized> Clone for WithComparator<'_, C, Q> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<C: ?Sized, Q: ?Sized> Copy for WithComparator<'_, C, Q> {}
+
+impl<C: ?Sized, Q: ?Sized + fmt::Debug> fmt::Debug for WithComparator<'_, C, Q> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("WithComparator").field(&self.query).finish()
+  }
+}
+
+impl<K, C, Q> Equivalent<WithComparator<'_, C, Q>> for K
+where
+  K: ?Sized,
+  C: ?Sized + Comparator<K, Q>,
+  Q: ?Sized,
+{
+  #[inline]
+  fn equivalent(&self, key: &WithComparator<'_, C, Q>) -> bool {
+    key.comparator.equivalent(self, key.query)
+  }
+}
+
+impl<K, C, Q> Comparable<WithComparator<'_, C, Q>> for K
+where
+  K: ?Sized,
+  C: ?Sized + Comparator<K, Q>,
+  Q: ?Sized,
+{
+  #[inline]
+  fn compare(&self, key: &WithComparator<'_, C, Q>) -> Ordering {
+    key.comparator.compare(self, key.query)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::cmp::Ordering;
+  use std::{boxed::Box, string::String, vec::Vec};
+
+  use super::{ByComparable, Comparator, WithComparator};
+  use crate::{AsciiCaseInsensitive, Comparable, Equivalent, SliceExt};
+
+  fn collations() -> Vec<Box<dyn Comparator<String, str>>> {
+    Vec::from([
+      Box::new(ByComparable) as Box<dyn Comparator<String, str>>,
+      Box::new(|k: &String, q: &str| {
+        AsciiCaseInsensitive::from_ref(k.as_str()).cmp(AsciiCaseInsensitive::from_ref(q))
+      }),
+    ])
+  }
+
+  #[test]
+  fn runtime_comparators() {
+    let keys: Vec<String> = ["Apple", "banana", "Cherry"]
+      .iter()
+      .map(|s| String::from(*s))
+      .collect();
+    let collations = collations();
+    let (binary, folded) = (&*collations[0], &*collations[1]);
+
+    assert!(binary.equivalent(&keys[1], "banana"));
+    assert!(!binary.equivalent(&keys[1], "BANANA"));
+    assert!(folded.equivalent(&keys[1], "BANANA"));
+    assert_eq!(binary.compare(&keys[0], "apple"), Ordering::Less);
+    assert_eq!(folded.compare(&keys[0], "apple"), Ordering::Equal);
+
+    // Sorted case-insensitively, but not by the binary order.
+    assert_eq!(
+      keys.binary_search_comparable(&WithComparator::new(folded, "CHERRY")),
+      Ok(2)
+    );
+    assert_eq!(
+      keys.binary_search_comparable(&WithComparator::new(folded, "blueberry")),
+      Err(2)
+    );
+  }
+
+  #[test]
+  fn with_comparator_bridges_to_comparable() {
+    let cmp = |k: &u32, q: &u32| k.cmp(q).reverse();
+    let query = WithComparator::new(&cmp, &3);
+    assert_eq!(*query.query(), 3);
+    assert_eq!(5.compare(&query), Ordering::Less);
+    assert!(3.equivalent(&query));
+
+    let by = WithComparator::new(&ByComparable, "b");
+    assert_eq!(String::from("a").compare(&by), Ordering::Less);
+    assert!(String::from("b").equivalent(&by));
+  }
+}
@@ -0,0 +1,135 @@
+use core::{
+  cmp::Ordering,
+  hash::{Hash, Hasher},
+};
+
+use super::{Comparable, Equivalent, EquivalentHash};
+
+/// A query wrapper for tuple keys, comparing them element by element.
+///
+/// A `(String, u32)` key does not `Borrow` a `(&str, u32)`, so the blanket
+/// implementations cannot look it up without building an owned key. Instead,
+/// wrap a tuple of references to the query elements, e.g.
+/// `Composite((name, &id))` where `name: &str`, and every tuple of up to 12
+/// keys `(K1, K2, ..)` implements [`Equivalent`], [`Comparable`] (in
+/// lexicographic order) and [`EquivalentHash`] against
+/// `Composite<(&Q1, &Q2, ..)>` whenever each `Ki` does against `Qi`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Composite<T>(pub T);
+
+macro_rules! impl_composite {
+  ($($k:ident $q:ident $idx:tt),+ $(,)?) => {
+    impl<'a, $($k, $q: ?Sized),+> Equivalent<Composite<($(&'a $q,)+)>> for ($($k,)+)
+    where
+      $($k: Equivalent<$q>),+
+    {
+      #[inline]
+      fn equivalent(&self, key: &Composite<($(&'a $q,)+)>) -> bool {
+        $(self.$idx.equivalent(key.0.$idx))&&+
+      }
+    }
+
+    impl<'a, $($k, $q: ?Sized),+> Comparable<Composite<($(&'a $q,)+)>> for ($($k,)+)
+    where
+      $($k: Comparable<$q>),+
+    {
+      #[inline]
+      fn compare(&self, key: &Composite<($(&'a $q,)+)>) -> Ordering {
+        $(
+          match self.$idx.compare(key.0.$idx) {
+            Ordering::Equal => {}
+            ord => return ord,
+          }
+        )+
+        Ordering::Equal
+      }
+    }
+
+    impl<'a, $($k, $q: ?Sized),+> EquivalentHash<Composite<($(&'a $q,)+)>> for ($($k,)+)
+    where
+      $($k: EquivalentHash<$q>, $q: Hash),+
+    {
+      #[inline]
+      fn hash_like<H: Hasher>(&self, state: &mut H) {
+        $(self.$idx.hash_like(state);)+
+      }
+    }
+  };
+}
+
+impl_composite!(K0 Q0 0);
+impl_composite!(K0 Q0 0, K1 Q1 1);
+impl_composite!(K0 Q0 0, K1 Q1 1, K2 Q2 2);
+impl_composite!(K0 Q0 0, K1 Q1 1, K2 Q2 2, K3 Q3 3);
+impl_composite!(K0 Q0 0, K1 Q1 1, K2 Q2 2, K3 Q3 3, K4 Q4 4);
+impl_composite!(K0 Q0 0, K1 Q1 1, K2 Q2 2, K3 Q3 3, K4 Q4 4, K5 Q5 5);
+impl_composite!(K0 Q0 0, K1 Q1 1, K2 Q2 2, K3 Q3 3, K4 Q4 4, K5 Q5 5, K6 Q6 6);
+impl_composite!(K0 Q0 0, K1 Q1 1, K2 Q2 2, K3 Q3 3, K4 Q4 4, K5 Q5 5, K6 Q6 6, K7 Q7 7);
+impl_composite!(
+  K0 Q0 0, K1 Q1 1, K2 Q2 2, K3 Q3 3, K4 Q4 4, K5 Q5 5, K6 Q6 6, K7 Q7 7, K8 Q8 8,
+);
+impl_composite!(
+  K0 Q0 0, K1 Q1 1, K2 Q2 2, K3 Q3 3, K4 Q4 4, K5 Q5 5, K6 Q6 6, K7 Q7 7, K8 Q8 8, K9 Q9 9,
+);
+impl_composite!(
+  K0 Q0 0, K1 Q1 1, K2 Q2 2, K3 Q3 3, K4 Q4 4, K5 Q5 5, K6 Q6 6, K7 Q7 7, K8 Q8 8, K9 Q9 9,
+  K10 Q10 10,
+);
+impl_composite!(
+  K0 Q0 0, K1 Q1 1, K2 Q2 2, K3 Q3 3, K4 Q4 4, K5 Q5 5, K6 Q6 6, K7 Q7 7, K8 Q8 8, K9 Q9 9,
+  K10 Q10 10, K11 Q11 11,
+);
+
+#[cfg(test)]
+mod tests {
+  use core::cmp::Ordering;
+  use std::{string::String, vec::Vec};
+
+  use super::Composite;
+  use crate::{
+    tests::{hash, hash_like},
+    Comparable, Equivalent,
+  };
+
+  #[test]
+  fn lexicographic_compare() {
+    let key = (String::from("b"), 2u32);
+    assert_eq!(key.compare(&Composite(("b", &2))), Ordering::Equal);
+    assert_eq!(key.compare(&Composite(("b", &3))), Ordering::Less);
+    assert_eq!(key.compare(&Composite(("a", &9))), Ordering::Greater);
+    assert_eq!(key.compare(&Composite(("c", &0))), Ordering::Less);
+    assert!(key.equivalent(&Composite(("b", &2))));
+    assert!(!key.equivalent(&Composite(("b", &1))));
+  }
+
+  #[test]
+  fn search_tuple_keys() {
+    let mut keys: Vec<(String, u32)> = [("a", 1), ("b", 1), ("b", 2), ("c", 0)]
+      .iter()
+      .map(|&(s, n)| (String::from(s), n))
+      .collect();
+    keys.sort();
+
+    let search = |s: &str, n: u32| keys.binary_search_by(|k| k.compare(&Composite((s, &n))));
+    assert_eq!(search("b", 2), Ok(2));
+    assert_eq!(search("b", 0), Err(1));
+    assert_eq!(search("d", 0), Err(4));
+  }
+
+  #[test]
+  fn hash_matches_query() {
+    let key = (String::from("b"), 2u32, Vec::from(&b"xy"[..]));
+    let query = Composite(("b", &2u32, &b"xy"[..]));
+    assert_eq!(
+      hash_like::<_, Composite<(&str, &u32, &[u8])>>(&key),
+      hash(&query)
+    );
+  }
+
+  #[test]
+  fn twelve_elements() {
+    let key = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+    let query = Composite((&0, &1, &2, &3, &4, &5, &6, &7, &8, &9, &10, &12));
+    assert_eq!(key.compare(&query), Ordering::Less);
+  }
+}
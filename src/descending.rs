@@ -0,0 +1,129 @@
+use core::{
+  cmp::{Ordering, Reverse},
+  hash::{Hash, Hasher},
+};
+
+use super::{Comparable, Equivalent, EquivalentHash};
+
+transparent_wrapper! {
+  /// A query wrapper for maps whose keys are stored as [`Reverse<K>`].
+  ///
+  /// The blanket [`Comparable`] implementation only works through `Borrow`, so
+  /// a `Reverse<String>` key cannot be compared against a `str`. Wrapping the
+  /// query in `Descending` inverts the ordering of `K: Comparable<Q>`, which is
+  /// exactly the order of the stored `Reverse<K>` keys.
+  ///
+  /// Ranges of `Descending<Q>` work with
+  /// [`ComparableRangeBounds`](crate::ComparableRangeBounds) as usual, keeping
+  /// in mind that the start of the range is the *greater* `Q`, e.g.
+  /// `Descending(3)..=Descending(1)` contains `Reverse(2)`.
+  #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+  pub struct Descending<Q: ?Sized>(pub Q);
+}
+
+impl<Q: ?Sized + PartialOrd> PartialOrd for Descending<Q> {
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    other.0.partial_cmp(&self.0)
+  }
+}
+
+impl<Q: ?Sized + Ord> Ord for Descending<Q> {
+  #[inline]
+  fn cmp(&self, other: &Self) -> Ordering {
+    other.0.cmp(&self.0)
+  }
+}
+
+impl<K, Q> Equivalent<Descending<Q>> for Reverse<K>
+where
+  K: Equivalent<Q>,
+  Q: ?Sized,
+{
+  #[inline]
+  fn equivalent(&self, key: &Descending<Q>) -> bool {
+    self.0.equivalent(&key.0)
+  }
+}
+
+impl<K, Q> Comparable<Descending<Q>> for Reverse<K>
+where
+  K: Comparable<Q>,
+  Q: ?Sized,
+{
+  #[inline]
+  fn compare(&self, key: &Descending<Q>) -> Ordering {
+    self.0.compare(&key.0).reverse()
+  }
+}
+
+impl<K, Q> EquivalentHash<Descending<Q>> for Reverse<K>
+where
+  K: EquivalentHash<Q>,
+  Q: ?Sized + Hash,
+{
+  #[inline]
+  fn hash_like<H: Hasher>(&self, state: &mut H) {
+    self.0.hash_like(state)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::cmp::{Ordering, Reverse};
+  use std::{string::String, vec::Vec};
+
+  use super::Descending;
+  use crate::{
+    tests::{hash, hash_like},
+    Comparable, ComparableRangeBounds, Equivalent,
+  };
+
+  #[test]
+  fn compare_is_reversed() {
+    let key = Reverse(String::from("b"));
+    assert_eq!(key.compare(Descending::from_ref("a")), Ordering::Less);
+    assert_eq!(key.compare(Descending::from_ref("b")), Ordering::Equal);
+    assert_eq!(key.compare(Descending::from_ref("c")), Ordering::Greater);
+    assert!(key.equivalent(Descending::from_ref("b")));
+    assert!(!key.equivalent(Descending::from_ref("c")));
+  }
+
+  #[test]
+  fn search_reverse_keys() {
+    let mut keys: Vec<_> = ["a", "b", "c"]
+      .iter()
+      .map(|s| Reverse(String::from(*s)))
+      .collect();
+    keys.sort();
+
+    let search = |q: &str| keys.binary_search_by(|k| k.compare(Descending::from_ref(q)));
+    assert_eq!(search("c"), Ok(0));
+    assert_eq!(search("a"), Ok(2));
+    assert_eq!(search("bb"), Err(1));
+    assert_eq!(search("d"), Err(0));
+    assert_eq!(search(""), Err(3));
+  }
+
+  #[test]
+  fn order_and_ranges() {
+    assert!(Descending(3) < Descending(1));
+    assert!(Descending(1.0) > Descending(2.0));
+
+    let range = Descending(3)..=Descending(1);
+    assert!(range.compare_contains(&Reverse(2)));
+    assert!(range.compare_contains(&Reverse(3)));
+    assert!(range.compare_contains(&Reverse(1)));
+    assert!(!range.compare_contains(&Reverse(4)));
+    assert!(!range.compare_contains(&Reverse(0)));
+  }
+
+  #[test]
+  fn hash_matches_query() {
+    let key = Reverse(String::from("b"));
+    assert_eq!(
+      hash_like::<_, Descending<str>>(&key),
+      hash(Descending::from_ref("b"))
+    );
+  }
+}
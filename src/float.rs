@@ -0,0 +1,183 @@
+use core::{
+  cmp::Ordering,
+  hash::{Hash, Hasher},
+};
+
+use super::{Comparable, Equivalent};
+
+macro_rules! total_float {
+  ($(#[$meta:meta])* $name:ident($float:ty, $bits:ty, $signed:ty, $sign_shift:literal)) => {
+    $(#[$meta])*
+    ///
+    /// Two values are equal if and only if they have the same bit pattern, so
+    /// `-0.0 < +0.0` and NaNs with different payloads are distinct, and the
+    /// ordering follows the IEEE 754 `totalOrder` predicate:
+    /// negative quiet NaN < negative signaling NaN < negative infinity <
+    /// negative numbers < `-0.0` < `+0.0` < positive numbers < positive
+    /// infinity < positive signaling NaN < positive quiet NaN.
+    ///
+    #[doc = concat!("It also implements [`Equivalent`] and [`Comparable`] against plain `", stringify!($float), "` queries.")]
+    #[derive(Debug, Default, Clone, Copy)]
+    #[repr(transparent)]
+    pub struct $name(pub $float);
+
+    impl $name {
+      /// Returns the key of this value in `totalOrder`, as a signed integer.
+      #[inline]
+      fn total_key(value: $float) -> $signed {
+        let bits = value.to_bits() as $signed;
+        // Flip all the bits but the sign of negative values, so that the
+        // two's complement order of the bits is the `totalOrder`.
+        bits ^ ((((bits >> $sign_shift) as $bits) >> 1) as $signed)
+      }
+    }
+
+    impl From<$float> for $name {
+      #[inline]
+      fn from(value: $float) -> Self {
+        Self(value)
+      }
+    }
+
+    impl From<$name> for $float {
+      #[inline]
+      fn from(value: $name) -> Self {
+        value.0
+      }
+    }
+
+    impl PartialEq for $name {
+      #[inline]
+      fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+      }
+    }
+
+    impl Eq for $name {}
+
+    impl PartialOrd for $name {
+      #[inline]
+      fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+      }
+    }
+
+    impl Ord for $name {
+      #[inline]
+      fn cmp(&self, other: &Self) -> Ordering {
+        Self::total_key(self.0).cmp(&Self::total_key(other.0))
+      }
+    }
+
+    impl Hash for $name {
+      #[inline]
+      fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state)
+      }
+    }
+
+    impl Equivalent<$float> for $name {
+      #[inline]
+      fn equivalent(&self, key: &$float) -> bool {
+        self.0.to_bits() == key.to_bits()
+      }
+    }
+
+    impl Comparable<$float> for $name {
+      #[inline]
+      fn compare(&self, key: &$float) -> Ordering {
+        Self::total_key(self.0).cmp(&Self::total_key(*key))
+      }
+    }
+  };
+}
+
+total_float!(
+  /// An `f32` with a total order, usable as a key of ordered maps.
+  TotalF32(f32, u32, i32, 31)
+);
+
+total_float!(
+  /// An `f64` with a total order, usable as a key of ordered maps.
+  TotalF64(f64, u64, i64, 63)
+);
+
+#[cfg(test)]
+mod tests {
+  use core::{cmp::Ordering, f64};
+  use std::vec::Vec;
+
+  use super::{TotalF32, TotalF64};
+  use crate::{
+    tests::hash, Comparable, Equivalent, PartialComparable, PartialComparableRangeBounds,
+  };
+
+  fn samples() -> Vec<f64> {
+    Vec::from([
+      -f64::NAN,
+      f64::NEG_INFINITY,
+      f64::MIN,
+      -1.5,
+      -f64::MIN_POSITIVE,
+      -0.0,
+      0.0,
+      f64::MIN_POSITIVE,
+      1.0,
+      f64::MAX,
+      f64::INFINITY,
+      f64::NAN,
+      // A NaN with another payload.
+      f64::from_bits(f64::NAN.to_bits() | 1),
+    ])
+  }
+
+  #[test]
+  fn total_order_f64() {
+    for &a in &samples() {
+      for &b in &samples() {
+        let expected = a.total_cmp(&b);
+        assert_eq!(TotalF64(a).cmp(&TotalF64(b)), expected, "{} {}", a, b);
+        assert_eq!(TotalF64(a).compare(&b), expected, "{} {}", a, b);
+        assert_eq!(TotalF64(a).equivalent(&b), expected == Ordering::Equal);
+        assert_eq!(TotalF64(a) == TotalF64(b), expected == Ordering::Equal);
+      }
+    }
+  }
+
+  #[test]
+  fn total_order_f32() {
+    for &a in &samples() {
+      for &b in &samples() {
+        let (a, b) = (a as f32, b as f32);
+        let expected = a.total_cmp(&b);
+        assert_eq!(TotalF32(a).cmp(&TotalF32(b)), expected, "{} {}", a, b);
+        assert_eq!(TotalF32(a).compare(&b), expected, "{} {}", a, b);
+      }
+    }
+  }
+
+  #[test]
+  fn signed_zeros_and_nans() {
+    assert!(TotalF64(-0.0) < TotalF64(0.0));
+    assert_ne!(TotalF64(-0.0), TotalF64(0.0));
+    assert_eq!(TotalF64(f64::NAN), TotalF64(f64::NAN));
+    assert_eq!(hash(&TotalF64(f64::NAN)), hash(&TotalF64(f64::NAN)));
+    assert_ne!(hash(&TotalF64(-0.0)), hash(&TotalF64(0.0)));
+  }
+
+  #[test]
+  fn partial_compare() {
+    assert_eq!(1.0.partial_compare(&2.0), Some(Ordering::Less));
+    assert_eq!(f64::NAN.partial_compare(&2.0), None);
+    assert_eq!((-0.0).partial_compare(&0.0), Some(Ordering::Equal));
+
+    let range = 1.0..=2.0;
+    assert!(range.partial_compare_contains(&1.0));
+    assert!(range.partial_compare_contains(&2.0));
+    assert!(!range.partial_compare_contains(&2.5));
+    assert!(!range.partial_compare_contains(&f64::NAN));
+    assert!(!(f64::NAN..).partial_compare_contains(&1.0));
+    assert!((..1.0).partial_compare_contains(&f64::NEG_INFINITY));
+    assert!(!(..1.0).partial_compare_contains(&1.0));
+  }
+}
@@ -0,0 +1,1020 @@
+//! A hash table with open addressing, which can be queried with any `Q` where
+//! `K: EquivalentHash<Q>`.
+//!
+//! The entries are stored densely in a vector, and a table of indices into it
+//! is probed linearly, so iterating is as fast as iterating a vector. Removing
+//! an entry moves the last entry into its place, so the iteration order is
+//! unspecified.
+//!
+//! The table relies on the hashing contracts of [`Equivalent`] and
+//! [`EquivalentHash`]: entries are hashed by the `Hash` implementation of
+//! their key on insertion, and looked up by the hash of the query, so when
+//! `key.equivalent(query)` returns `true`, both `key.hash(..)` and
+//! `key.hash_like(..)` must feed the hasher the same data as `query.hash(..)`.
+//!
+//! The table does not enforce this: a key which does not hash like its queries
+//! is silently missed, in every build. Debug builds only catch some mistakes,
+//! as a lookup which finds a key asserts that `key.hash_like(..)` matches the
+//! hash of the query, and inserting into a vacant [`Entry`] asserts that the
+//! new key hashes like, and is equivalent to, the query it was looked up with.
+
+use alloc::vec::{self, Vec};
+use core::{
+  fmt,
+  hash::{BuildHasher, Hash, Hasher},
+  iter::{FromIterator, FusedIterator},
+  mem, slice,
+};
+
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
+
+use super::{Equivalent, EquivalentHash};
+
+/// Marks an empty slot of the index table.
+const EMPTY: usize = !0;
+
+/// The minimum number of slots of a non-empty index table.
+const MIN_SLOTS: usize = 8;
+
+#[derive(Clone)]
+struct Bucket<K, V> {
+  hash: u64,
+  key: K,
+  value: V,
+}
+
+/// A hash map with open addressing and linear probing.
+///
+/// Every lookup accepts any `Q` where `K: EquivalentHash<Q>`, as long as `K`
+/// also hashes like `Q` through its `Hash` implementation, see the
+/// [module documentation](self).
+#[derive(Clone)]
+pub struct HashTable<K, V, S> {
+  entries: Vec<Bucket<K, V>>,
+  indices: Vec<usize>,
+  hash_builder: S,
+}
+
+impl<K, V, S: Default> Default for HashTable<K, V, S> {
+  #[inline]
+  fn default() -> Self {
+    Self::with_hasher(S::default())
+  }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for HashTable<K, V, S> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<K, V> HashTable<K, V, RandomState> {
+  /// Creates an empty table, hashing with a [`RandomState`].
+  #[inline]
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+
+  /// Creates an empty table with space for at least `capacity` entries,
+  /// hashing with a [`RandomState`].
+  #[inline]
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_capacity_and_hasher(capacity, RandomState::new())
+  }
+}
+
+impl<K, V, S> HashTable<K, V, S> {
+  /// Creates an empty table, hashing with `hash_builder`.
+  #[inline]
+  pub fn with_hasher(hash_builder: S) -> Self {
+    Self {
+      entries: Vec::new(),
+      indices: Vec::new(),
+      hash_builder,
+    }
+  }
+
+  /// Creates an empty table with space for at least `capacity` entries,
+  /// hashing with `hash_builder`.
+  #[inline]
+  pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+    let mut this = Self::with_hasher(hash_builder);
+    if capacity > 0 {
+      this.entries.reserve_exact(capacity);
+      this.rebuild(slots_for(capacity));
+    }
+    this
+  }
+
+  /// Returns the hasher builder of the table.
+  #[inline]
+  pub fn hasher(&self) -> &S {
+    &self.hash_builder
+  }
+
+  /// Returns the number of entries in the table.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  /// Returns `true` if the table contains no entries.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  /// Returns the number of entries the table can hold without growing its
+  /// index table.
+  #[inline]
+  pub fn capacity(&self) -> usize {
+    max_load(self.indices.len())
+  }
+
+  /// Removes all the entries of the table, keeping its capacity.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.entries.clear();
+    for slot in &mut self.indices {
+      *slot = EMPTY;
+    }
+  }
+
+  /// Returns an iterator over the entries of the table.
+  #[inline]
+  pub fn iter(&self) -> Iter<'_, K, V> {
+    Iter {
+      inner: self.entries.iter(),
+    }
+  }
+
+  /// Returns an iterator over the entries of the table, with mutable
+  /// references to the values.
+  #[inline]
+  pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+    IterMut {
+      inner: self.entries.iter_mut(),
+    }
+  }
+
+  /// Returns an iterator over the keys of the table.
+  #[inline]
+  pub fn keys(&self) -> Keys<'_, K, V> {
+    Keys {
+      inner: self.entries.iter(),
+    }
+  }
+
+  /// Returns an iterator over the values of the table.
+  #[inline]
+  pub fn values(&self) -> Values<'_, K, V> {
+    Values {
+      inner: self.entries.iter(),
+    }
+  }
+
+  /// Retains only the entries for which `f` returns `true`.
+  pub fn retain<F>(&mut self, mut f: F)
+  where
+    F: FnMut(&K, &mut V) -> bool,
+  {
+    let len = self.entries.len();
+    let mut index = 0;
+    while index < self.entries.len() {
+      let bucket = &mut self.entries[index];
+      if f(&bucket.key, &mut bucket.value) {
+        index += 1;
+      } else {
+        self.entries.swap_remove(index);
+      }
+    }
+    if self.entries.len() != len {
+      let slots = self.indices.len();
+      self.rebuild(slots);
+    }
+  }
+
+  /// Rebuilds the index table with `slots` slots, which must be a power of
+  /// two greater than the number of entries.
+  fn rebuild(&mut self, slots: usize) {
+    self.indices.clear();
+    self.indices.resize(slots, EMPTY);
+    for index in 0..self.entries.len() {
+      let slot = self.empty_slot(self.entries[index].hash);
+      self.indices[slot] = index;
+    }
+  }
+
+  /// Returns the first empty slot of the probe sequence of `hash`.
+  fn empty_slot(&self, hash: u64) -> usize {
+    let mask = self.indices.len() - 1;
+    let mut slot = hash as usize & mask;
+    while self.indices[slot] != EMPTY {
+      slot = (slot + 1) & mask;
+    }
+    slot
+  }
+
+  /// Makes room for `additional` more entries.
+  fn reserve_slots(&mut self, additional: usize) {
+    let needed = self
+      .entries
+      .len()
+      .checked_add(additional)
+      .expect("capacity overflow");
+    if needed > self.capacity() {
+      self.rebuild(slots_for(needed));
+    }
+  }
+
+  /// Returns the slot and the index of the entry equivalent to `key`, or the
+  /// empty slot where it belongs.
+  fn find<Q>(&self, hash: u64, key: &Q) -> Result<(usize, usize), usize>
+  where
+    K: Equivalent<Q>,
+    Q: ?Sized,
+  {
+    if self.indices.is_empty() {
+      return Err(0);
+    }
+
+    let mask = self.indices.len() - 1;
+    let mut slot = hash as usize & mask;
+    loop {
+      let index = self.indices[slot];
+      if index == EMPTY {
+        return Err(slot);
+      }
+      let bucket = &self.entries[index];
+      if bucket.hash == hash && bucket.key.equivalent(key) {
+        return Ok((slot, index));
+      }
+      slot = (slot + 1) & mask;
+    }
+  }
+
+  /// Returns the slot which holds `index`.
+  fn slot_of(&self, index: usize) -> usize {
+    let mask = self.indices.len() - 1;
+    let mut slot = self.entries[index].hash as usize & mask;
+    while self.indices[slot] != index {
+      slot = (slot + 1) & mask;
+    }
+    slot
+  }
+
+  /// Removes the entry at `index`, held by `slot`.
+  fn remove_at(&mut self, slot: usize, index: usize) -> (K, V) {
+    // Shift the following entries of the probe sequence backwards, so that no
+    // entry is ever separated from its ideal slot by an empty one.
+    let mask = self.indices.len() - 1;
+    let mut hole = slot;
+    let mut next = (hole + 1) & mask;
+    loop {
+      let moved = self.indices[next];
+      if moved == EMPTY {
+        break;
+      }
+      let ideal = self.entries[moved].hash as usize & mask;
+      if next.wrapping_sub(ideal) & mask >= next.wrapping_sub(hole) & mask {
+        self.indices[hole] = moved;
+        hole = next;
+      }
+      next = (next + 1) & mask;
+    }
+    self.indices[hole] = EMPTY;
+
+    let last = self.entries.len() - 1;
+    if index != last {
+      let slot = self.slot_of(last);
+      self.indices[slot] = index;
+    }
+    let bucket = self.entries.swap_remove(index);
+    (bucket.key, bucket.value)
+  }
+}
+
+impl<K, V, S: BuildHasher> HashTable<K, V, S> {
+  /// Reserves capacity for at least `additional` more entries.
+  #[inline]
+  pub fn reserve(&mut self, additional: usize) {
+    self.entries.reserve(additional);
+    self.reserve_slots(additional);
+  }
+
+  /// Returns the entry with a key equivalent to `key`.
+  #[inline]
+  pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+  where
+    K: EquivalentHash<Q>,
+    Q: ?Sized + Hash,
+  {
+    let (_, index) = self.lookup(key).1.ok()?;
+    let bucket = &self.entries[index];
+    Some((&bucket.key, &bucket.value))
+  }
+
+  /// Returns a reference to the value of the key equivalent to `key`.
+  #[inline]
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: EquivalentHash<Q>,
+    Q: ?Sized + Hash,
+  {
+    self.get_key_value(key).map(|(_, v)| v)
+  }
+
+  /// Returns a mutable reference to the value of the key equivalent to `key`.
+  #[inline]
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: EquivalentHash<Q>,
+    Q: ?Sized + Hash,
+  {
+    let (_, index) = self.lookup(key).1.ok()?;
+    Some(&mut self.entries[index].value)
+  }
+
+  /// Returns `true` if the table contains a key equivalent to `key`.
+  #[inline]
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: EquivalentHash<Q>,
+    Q: ?Sized + Hash,
+  {
+    self.lookup(key).1.is_ok()
+  }
+
+  /// Removes the entry with a key equivalent to `key`, returning its value.
+  #[inline]
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: EquivalentHash<Q>,
+    Q: ?Sized + Hash,
+  {
+    self.remove_entry(key).map(|(_, v)| v)
+  }
+
+  /// Removes the entry with a key equivalent to `key`, returning it.
+  #[inline]
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: EquivalentHash<Q>,
+    Q: ?Sized + Hash,
+  {
+    let (slot, index) = self.lookup(key).1.ok()?;
+    Some(self.remove_at(slot, index))
+  }
+
+  /// Returns the entry of the key equivalent to `key`, for in-place
+  /// manipulation.
+  ///
+  /// A vacant entry has room reserved for its key, so that inserting into it
+  /// does not probe the table again.
+  #[inline]
+  pub fn entry<'q, Q>(&mut self, key: &'q Q) -> Entry<'_, 'q, K, V, S, Q>
+  where
+    K: EquivalentHash<Q>,
+    Q: ?Sized + Hash,
+  {
+    let (hash, found) = self.lookup(key);
+    match found {
+      Ok((slot, index)) => Entry::Occupied(OccupiedEntry {
+        table: self,
+        slot,
+        index,
+      }),
+      Err(mut slot) => {
+        if self.entries.len() == self.capacity() {
+          // Growing rebuilds the index table, which moves the empty slots.
+          self.reserve_slots(1);
+          slot = self.empty_slot(hash);
+        }
+        Entry::Vacant(VacantEntry {
+          table: self,
+          hash,
+          slot,
+          key,
+        })
+      }
+    }
+  }
+
+  /// Inserts a key-value pair into the table, returning the previous value of
+  /// the key, if any.
+  ///
+  /// As with the standard maps, the key itself is not updated if it was
+  /// already present.
+  #[inline]
+  pub fn insert(&mut self, key: K, value: V) -> Option<V>
+  where
+    K: Hash + Eq,
+  {
+    self.reserve_slots(1);
+    let hash = self.hash(&key);
+    match self.find(hash, &key) {
+      Ok((_, index)) => Some(mem::replace(&mut self.entries[index].value, value)),
+      Err(slot) => {
+        self.indices[slot] = self.entries.len();
+        self.entries.push(Bucket { hash, key, value });
+        None
+      }
+    }
+  }
+
+  /// Hashes `key`, and returns its hash along with the result of `find`.
+  ///
+  /// In debug builds, asserts that the key found hashes like `key` through
+  /// `EquivalentHash` too. This only checks keys which are found: a key whose
+  /// `Hash` differs from that of `key` is missed without any check, and
+  /// release builds check nothing.
+  #[inline]
+  fn lookup<Q>(&self, key: &Q) -> (u64, Result<(usize, usize), usize>)
+  where
+    K: EquivalentHash<Q>,
+    Q: ?Sized + Hash,
+  {
+    let hash = self.hash(key);
+    let found = self.find(hash, key);
+    if let Ok((_, index)) = found {
+      debug_assert!(
+        {
+          let mut state = self.hash_builder.build_hasher();
+          self.entries[index].key.hash_like(&mut state);
+          state.finish() == hash
+        },
+        "the key does not hash like the query it was looked up with"
+      );
+    }
+    (hash, found)
+  }
+
+  #[inline]
+  fn hash<Q: ?Sized + Hash>(&self, key: &Q) -> u64 {
+    let mut state = self.hash_builder.build_hasher();
+    key.hash(&mut state);
+    state.finish()
+  }
+}
+
+/// Returns the number of entries an index table with `slots` slots holds
+/// before it grows, i.e. a load factor of 7/8.
+#[inline]
+fn max_load(slots: usize) -> usize {
+  slots - slots / 8
+}
+
+/// Returns the number of slots of an index table which holds `len` entries.
+#[inline]
+fn slots_for(len: usize) -> usize {
+  let mut slots = MIN_SLOTS;
+  while max_load(slots) < len {
+    slots = slots.checked_mul(2).expect("capacity overflow");
+  }
+  slots
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> FromIterator<(K, V)> for HashTable<K, V, S> {
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    let mut table = Self::default();
+    table.extend(iter);
+    table
+  }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Extend<(K, V)> for HashTable<K, V, S> {
+  fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0);
+    for (k, v) in iter {
+      self.insert(k, v);
+    }
+  }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a HashTable<K, V, S> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = Iter<'a, K, V>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut HashTable<K, V, S> {
+  type Item = (&'a K, &'a mut V);
+  type IntoIter = IterMut<'a, K, V>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+impl<K, V, S> IntoIterator for HashTable<K, V, S> {
+  type Item = (K, V);
+  type IntoIter = IntoIter<K, V>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    IntoIter {
+      inner: self.entries.into_iter(),
+    }
+  }
+}
+
+macro_rules! bucket_iterator {
+  ($(#[$meta:meta])* $name:ident<$($lt:lifetime,)? K, V>($inner:ty) => $item:ty, |$b:ident| $map:expr) => {
+    $(#[$meta])*
+    pub struct $name<$($lt,)? K, V> {
+      inner: $inner,
+    }
+
+    impl<$($lt,)? K, V> Iterator for $name<$($lt,)? K, V> {
+      type Item = $item;
+
+      #[inline]
+      fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|$b| $map)
+      }
+
+      #[inline]
+      fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+      }
+    }
+
+    impl<$($lt,)? K, V> DoubleEndedIterator for $name<$($lt,)? K, V> {
+      #[inline]
+      fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|$b| $map)
+      }
+    }
+
+    impl<$($lt,)? K, V> ExactSizeIterator for $name<$($lt,)? K, V> {}
+
+    impl<$($lt,)? K, V> FusedIterator for $name<$($lt,)? K, V> {}
+  };
+}
+
+bucket_iterator!(
+  /// An iterator over the entries of a [`HashTable`].
+  Iter<'a, K, V>(slice::Iter<'a, Bucket<K, V>>) => (&'a K, &'a V),
+  |b| (&b.key, &b.value)
+);
+
+bucket_iterator!(
+  /// An iterator over the entries of a [`HashTable`], with mutable references
+  /// to the values.
+  IterMut<'a, K, V>(slice::IterMut<'a, Bucket<K, V>>) => (&'a K, &'a mut V),
+  |b| (&b.key, &mut b.value)
+);
+
+bucket_iterator!(
+  /// An owning iterator over the entries of a [`HashTable`].
+  IntoIter<K, V>(vec::IntoIter<Bucket<K, V>>) => (K, V),
+  |b| (b.key, b.value)
+);
+
+bucket_iterator!(
+  /// An iterator over the keys of a [`HashTable`].
+  Keys<'a, K, V>(slice::Iter<'a, Bucket<K, V>>) => &'a K,
+  |b| &b.key
+);
+
+bucket_iterator!(
+  /// An iterator over the values of a [`HashTable`].
+  Values<'a, K, V>(slice::Iter<'a, Bucket<K, V>>) => &'a V,
+  |b| &b.value
+);
+
+impl<K, V> Clone for Iter<'_, K, V> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+    }
+  }
+}
+
+/// A view into a single entry of a [`HashTable`], which may either be vacant
+/// or occupied.
+pub enum Entry<'a, 'q, K, V, S, Q: ?Sized> {
+  /// An occupied entry.
+  Occupied(OccupiedEntry<'a, K, V, S>),
+  /// A vacant entry.
+  Vacant(VacantEntry<'a, 'q, K, V, S, Q>),
+}
+
+impl<'a, K, V, S, Q> Entry<'a, '_, K, V, S, Q>
+where
+  K: Hash + Equivalent<Q>,
+  S: BuildHasher,
+  Q: ?Sized + Hash + alloc::borrow::ToOwned<Owned = K>,
+{
+  /// Ensures a value is in the entry by inserting `default` if empty, and
+  /// returns a mutable reference to the value.
+  #[inline]
+  pub fn or_insert(self, default: V) -> &'a mut V {
+    match self {
+      Entry::Occupied(entry) => entry.into_mut(),
+      Entry::Vacant(entry) => entry.insert(default),
+    }
+  }
+
+  /// Ensures a value is in the entry by inserting the result of `default` if
+  /// empty, and returns a mutable reference to the value.
+  #[inline]
+  pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+  where
+    F: FnOnce() -> V,
+  {
+    match self {
+      Entry::Occupied(entry) => entry.into_mut(),
+      Entry::Vacant(entry) => entry.insert(default()),
+    }
+  }
+
+  /// Ensures a value is in the entry by inserting `V::default()` if empty, and
+  /// returns a mutable reference to the value.
+  #[inline]
+  pub fn or_default(self) -> &'a mut V
+  where
+    V: Default,
+  {
+    self.or_insert_with(V::default)
+  }
+}
+
+impl<K, V, S, Q: ?Sized> Entry<'_, '_, K, V, S, Q> {
+  /// Calls `f` with the value of an occupied entry, before any potential
+  /// insert into the table.
+  #[inline]
+  pub fn and_modify<F>(mut self, f: F) -> Self
+  where
+    F: FnOnce(&mut V),
+  {
+    if let Entry::Occupied(entry) = &mut self {
+      f(entry.get_mut());
+    }
+    self
+  }
+}
+
+/// A view into an occupied entry of a [`HashTable`].
+pub struct OccupiedEntry<'a, K, V, S> {
+  table: &'a mut HashTable<K, V, S>,
+  slot: usize,
+  index: usize,
+}
+
+impl<'a, K, V, S> OccupiedEntry<'a, K, V, S> {
+  /// Returns the key of the entry.
+  #[inline]
+  pub fn key(&self) -> &K {
+    &self.table.entries[self.index].key
+  }
+
+  /// Returns a reference to the value of the entry.
+  #[inline]
+  pub fn get(&self) -> &V {
+    &self.table.entries[self.index].value
+  }
+
+  /// Returns a mutable reference to the value of the entry.
+  #[inline]
+  pub fn get_mut(&mut self) -> &mut V {
+    &mut self.table.entries[self.index].value
+  }
+
+  /// Converts the entry into a mutable reference to its value, with the
+  /// lifetime of the table.
+  #[inline]
+  pub fn into_mut(self) -> &'a mut V {
+    &mut self.table.entries[self.index].value
+  }
+
+  /// Sets the value of the entry, returning the old value.
+  #[inline]
+  pub fn insert(&mut self, value: V) -> V {
+    mem::replace(self.get_mut(), value)
+  }
+
+  /// Removes the entry from the table, returning its value.
+  #[inline]
+  pub fn remove(self) -> V {
+    self.remove_entry().1
+  }
+
+  /// Removes the entry from the table, returning it.
+  #[inline]
+  pub fn remove_entry(self) -> (K, V) {
+    self.table.remove_at(self.slot, self.index)
+  }
+}
+
+/// A view into a vacant entry of a [`HashTable`].
+pub struct VacantEntry<'a, 'q, K, V, S, Q: ?Sized> {
+  table: &'a mut HashTable<K, V, S>,
+  hash: u64,
+  slot: usize,
+  key: &'q Q,
+}
+
+impl<'a, 'q, K, V, S, Q: ?Sized> VacantEntry<'a, 'q, K, V, S, Q> {
+  /// Returns the key the entry was looked up with.
+  #[inline]
+  pub fn key(&self) -> &'q Q {
+    self.key
+  }
+
+  /// Inserts the entry into the table, with the key created by
+  /// `Q::to_owned`, and returns a mutable reference to its value.
+  #[inline]
+  pub fn insert(self, value: V) -> &'a mut V
+  where
+    K: Hash + Equivalent<Q>,
+    S: BuildHasher,
+    Q: Hash + alloc::borrow::ToOwned<Owned = K>,
+  {
+    let key = self.key.to_owned();
+    self.insert_with_key(key, value)
+  }
+
+  /// Inserts the entry into the table with the given key, which must be
+  /// equivalent to, and hash like, the key the entry was looked up with, and
+  /// returns a mutable reference to its value.
+  ///
+  /// # Panics
+  ///
+  /// In debug builds, panics if `key` is not equivalent to the key the entry
+  /// was looked up with, or does not hash like it.
+  #[inline]
+  pub fn insert_with_key(self, key: K, value: V) -> &'a mut V
+  where
+    K: Hash + Equivalent<Q>,
+    S: BuildHasher,
+    Q: Hash,
+  {
+    debug_assert!(
+      key.equivalent(self.key),
+      "the key is not equivalent to the query it was looked up with"
+    );
+    debug_assert!(
+      self.table.hash(&key) == self.hash,
+      "the key does not hash like the query it was looked up with"
+    );
+
+    let index = self.table.entries.len();
+    self.table.indices[self.slot] = index;
+    self.table.entries.push(Bucket {
+      hash: self.hash,
+      key,
+      value,
+    });
+    &mut self.table.entries[index].value
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::hash::{BuildHasherDefault, Hash, Hasher};
+  use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    string::{String, ToString},
+    vec::Vec,
+  };
+
+  use super::{Entry, HashTable};
+  use crate::{tests::xorshift, Equivalent, EquivalentHash, Sequence};
+
+  type Deterministic = BuildHasherDefault<DefaultHasher>;
+
+  /// Hashes everything into four values, so that probe chains collide and
+  /// overlap.
+  #[derive(Default)]
+  struct Colliding(u64);
+
+  impl Hasher for Colliding {
+    fn write(&mut self, bytes: &[u8]) {
+      for &b in bytes {
+        self.0 += u64::from(b);
+      }
+    }
+
+    fn finish(&self) -> u64 {
+      self.0 % 4
+    }
+  }
+
+  /// Mixes the length of every write into the hash, like `FxHasher` does, so
+  /// that the same bytes hash differently when written in different calls.
+  #[derive(Default)]
+  struct WriteSensitive(u64);
+
+  impl Hasher for WriteSensitive {
+    fn write(&mut self, bytes: &[u8]) {
+      for &b in bytes {
+        self.0 = (self.0.rotate_left(5) ^ u64::from(b)).wrapping_mul(0x517c_c1b7_2722_0a95);
+      }
+      self.0 = self.0.rotate_left(7) ^ bytes.len() as u64;
+    }
+
+    fn finish(&self) -> u64 {
+      self.0
+    }
+  }
+
+  /// A deterministic sequence of small keys, with many repeats.
+  fn keys() -> impl Iterator<Item = u32> {
+    xorshift().take(2000).map(|x| x % 128)
+  }
+
+  #[test]
+  fn matches_hash_map() {
+    let mut table: HashTable<String, usize, Deterministic> = HashTable::default();
+    let mut model = HashMap::new();
+    for (i, k) in keys().enumerate() {
+      let key = k.to_string();
+      match i % 3 {
+        0 | 1 => assert_eq!(table.insert(key.clone(), i), model.insert(key.clone(), i)),
+        _ => assert_eq!(table.remove(key.as_str()), model.remove(&key)),
+      }
+      assert_eq!(table.get(key.as_str()), model.get(&key));
+      assert_eq!(table.len(), model.len());
+    }
+
+    for k in 0..128 {
+      let key = k.to_string();
+      assert_eq!(table.get_key_value(key.as_str()), model.get_key_value(&key));
+      assert_eq!(table.contains_key(key.as_str()), model.contains_key(&key));
+    }
+    let entries: HashMap<_, _> = table.iter().map(|(k, &v)| (k.clone(), v)).collect();
+    assert_eq!(entries, model);
+  }
+
+  #[test]
+  fn removal_keeps_colliding_chains() {
+    let mut table: HashTable<u32, usize, BuildHasherDefault<Colliding>> = HashTable::default();
+    let mut model = HashMap::new();
+    for (i, k) in keys().enumerate() {
+      if i % 2 == 0 {
+        assert_eq!(table.insert(k, i), model.insert(k, i));
+      } else {
+        assert_eq!(table.remove_entry(&k), model.remove_entry(&k));
+      }
+      assert_eq!(table.len(), model.len());
+    }
+    assert!(table.len() > 8);
+
+    // Removing from the front of the chains shifts the rest back.
+    let mut present: Vec<_> = model.keys().copied().collect();
+    present.sort_unstable();
+    for k in present.into_iter().step_by(3) {
+      assert_eq!(table.remove(&k), model.remove(&k));
+      for (k, v) in &model {
+        assert_eq!(table.get(k), Some(v), "{:?}", k);
+      }
+    }
+    for k in 0..128 {
+      assert_eq!(table.get(&k), model.get(&k));
+    }
+  }
+
+  #[test]
+  fn entry_api() {
+    let mut table: HashTable<String, u32, Deterministic> = HashTable::default();
+    *table.entry("b").or_insert(0) += 1;
+    *table.entry("b").or_insert(0) += 1;
+    *table.entry("a").or_default() += 5;
+    table
+      .entry("c")
+      .and_modify(|v| *v += 1)
+      .or_insert_with(|| 7);
+    table
+      .entry("c")
+      .and_modify(|v| *v += 1)
+      .or_insert_with(|| 7);
+    assert_eq!(table.get("a"), Some(&5));
+    assert_eq!(table.get("b"), Some(&2));
+    assert_eq!(table.get("c"), Some(&8));
+
+    match table.entry("b") {
+      Entry::Occupied(mut entry) => {
+        assert_eq!(entry.key(), "b");
+        assert_eq!(entry.insert(10), 2);
+        assert_eq!(entry.remove_entry(), (String::from("b"), 10));
+      }
+      Entry::Vacant(_) => unreachable!(),
+    }
+    match table.entry("bb") {
+      Entry::Vacant(entry) => {
+        assert_eq!(entry.key(), "bb");
+        *entry.insert_with_key(String::from("bb"), 1) += 1;
+      }
+      Entry::Occupied(_) => unreachable!(),
+    }
+    assert_eq!(table.len(), 3);
+    assert_eq!(table.get("b"), None);
+    assert_eq!(table.get("bb"), Some(&2));
+  }
+
+  #[test]
+  fn entry_grows_only_when_vacant() {
+    let mut table: HashTable<u32, u32, BuildHasherDefault<Colliding>> = HashTable::default();
+    let mut k = 0;
+    while table.is_empty() || table.len() < table.capacity() {
+      *table.entry(&k).or_insert(0) += k;
+      k += 1;
+    }
+
+    let capacity = table.capacity();
+    *table.entry(&0).or_insert(0) += 1;
+    assert_eq!(table.capacity(), capacity);
+
+    *table.entry(&k).or_insert(0) += k;
+    assert!(table.capacity() > capacity);
+    assert_eq!(table.len(), k as usize + 1);
+    for k in 0..=k {
+      assert_eq!(table.get(&k), Some(&(k + (k == 0) as u32)));
+    }
+  }
+
+  #[test]
+  fn retain_and_clear() {
+    let mut table: HashTable<u32, u32, BuildHasherDefault<Colliding>> =
+      (0..100).map(|k| (k, k * 10)).collect();
+    table.retain(|k, v| {
+      *v += 1;
+      k % 3 == 0
+    });
+    assert_eq!(table.len(), 34);
+    for k in 0..100 {
+      let expected = k * 10 + 1;
+      assert_eq!(table.get(&k), Some(&expected).filter(|_| k % 3 == 0));
+    }
+
+    let capacity = table.capacity();
+    table.clear();
+    assert!(table.is_empty());
+    assert_eq!(table.capacity(), capacity);
+    assert_eq!(table.get(&3), None);
+    table.insert(3, 0);
+    assert_eq!(table.get(&3), Some(&0));
+  }
+
+  #[test]
+  fn sequence_queries() {
+    let mut table: HashTable<Vec<u8>, u32, BuildHasherDefault<WriteSensitive>> =
+      HashTable::default();
+    table.insert(Vec::from(&[1, 2, 3][..]), 0);
+    assert_eq!(table.get(Sequence::from_ref(&[1, 2, 3][..])), Some(&0));
+    assert_eq!(table.get(Sequence::from_ref(&[1, 2][..])), None);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn os_str_queries() {
+    use std::ffi::OsString;
+
+    use crate::OsStrLike;
+
+    let mut table = HashTable::new();
+    table.insert(OsString::from("foo"), 0);
+    assert_eq!(table.get(OsStrLike::from_ref("foo")), Some(&0));
+    #[cfg(unix)]
+    assert_eq!(table.get(OsStrLike::from_ref(&b"foo"[..])), Some(&0));
+    assert_eq!(table.get(OsStrLike::from_ref("fo")), None);
+  }
+
+  #[derive(PartialEq, Eq, Hash)]
+  struct Key(u32);
+
+  #[derive(Hash)]
+  struct Probe(u32);
+
+  impl Equivalent<Probe> for Key {
+    fn equivalent(&self, key: &Probe) -> bool {
+      self.0 == key.0
+    }
+  }
+
+  impl EquivalentHash<Probe> for Key {
+    fn hash_like<H: Hasher>(&self, state: &mut H) {
+      u64::from(self.0).hash(state)
+    }
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "does not hash like")]
+  fn lookup_with_mismatched_hash_like() {
+    let mut table: HashTable<Key, (), Deterministic> = HashTable::default();
+    table.insert(Key(1), ());
+    table.get(&Probe(1));
+  }
+}
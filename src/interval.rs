@@ -0,0 +1,93 @@
+use core::ops::{
+  Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
+
+/// An owned range with arbitrary bounds, e.g. as stored by an `IntervalMap` or
+/// a `RangeSet`.
+///
+/// It converts from every standard range type and from a pair of `Bound`s,
+/// and implements `RangeBounds<K>`, so it works with
+/// [`ComparableRangeBounds`](crate::ComparableRangeBounds).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<K> {
+  /// The lower bound of the interval.
+  pub start: Bound<K>,
+  /// The upper bound of the interval.
+  pub end: Bound<K>,
+}
+
+impl<K> Interval<K> {
+  /// Creates an interval from its bounds.
+  #[inline]
+  pub fn new(start: Bound<K>, end: Bound<K>) -> Self {
+    Self { start, end }
+  }
+
+  /// Creates the interval containing every item.
+  #[inline]
+  pub fn full() -> Self {
+    Self::new(Bound::Unbounded, Bound::Unbounded)
+  }
+}
+
+impl<K> RangeBounds<K> for Interval<K> {
+  #[inline]
+  fn start_bound(&self) -> Bound<&K> {
+    self.start.as_ref()
+  }
+
+  #[inline]
+  fn end_bound(&self) -> Bound<&K> {
+    self.end.as_ref()
+  }
+}
+
+impl<K> From<(Bound<K>, Bound<K>)> for Interval<K> {
+  #[inline]
+  fn from((start, end): (Bound<K>, Bound<K>)) -> Self {
+    Self::new(start, end)
+  }
+}
+
+impl<K> From<Range<K>> for Interval<K> {
+  #[inline]
+  fn from(range: Range<K>) -> Self {
+    Self::new(Bound::Included(range.start), Bound::Excluded(range.end))
+  }
+}
+
+impl<K> From<RangeInclusive<K>> for Interval<K> {
+  #[inline]
+  fn from(range: RangeInclusive<K>) -> Self {
+    let (start, end) = range.into_inner();
+    Self::new(Bound::Included(start), Bound::Included(end))
+  }
+}
+
+impl<K> From<RangeFrom<K>> for Interval<K> {
+  #[inline]
+  fn from(range: RangeFrom<K>) -> Self {
+    Self::new(Bound::Included(range.start), Bound::Unbounded)
+  }
+}
+
+impl<K> From<RangeTo<K>> for Interval<K> {
+  #[inline]
+  fn from(range: RangeTo<K>) -> Self {
+    Self::new(Bound::Unbounded, Bound::Excluded(range.end))
+  }
+}
+
+impl<K> From<RangeToInclusive<K>> for Interval<K> {
+  #[inline]
+  fn from(range: RangeToInclusive<K>) -> Self {
+    Self::new(Bound::Unbounded, Bound::Included(range.end))
+  }
+}
+
+impl<K> From<RangeFull> for Interval<K> {
+  #[inline]
+  fn from(_: RangeFull) -> Self {
+    Self::full()
+  }
+}
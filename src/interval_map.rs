@@ -0,0 +1,607 @@
+//! A map keyed by intervals, which finds the intervals containing a point or
+//! overlapping a range given with any `Q` where `K: Comparable<Q>`.
+//!
+//! The entries are kept in a vector sorted by interval, i.e. by start and then
+//! by end, alongside the running maximum of their ends, so a query skips the
+//! entries which end before it as well as those which start after it with
+//! binary searches.
+//!
+//! As with [`ComparableRangeBounds`], intervals are treated as intervals of a
+//! dense order, e.g. `(Excluded(1), Excluded(2))` overlaps `1.5..1.6` and is not
+//! empty even for integers.
+
+use alloc::vec::{self, Vec};
+use core::{
+  cmp::Ordering,
+  fmt,
+  iter::{FromIterator, FusedIterator},
+  mem,
+  ops::{Bound, RangeBounds},
+  slice,
+};
+
+use super::{
+  cmp_lower, cmp_upper, lower_reaches_upper, Comparable, ComparableRangeBounds, Interval,
+};
+
+/// A map from intervals with `K` endpoints to values.
+///
+/// Intervals are unique: inserting an interval which is already present
+/// replaces its value. Exact lookups and removals accept any `R:
+/// RangeBounds<Q>`, and queries any `Q`, where `K: Comparable<Q>`. The
+/// `Comparable<Q>` implementation must be consistent with the `Ord`
+/// implementation of `K`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalMap<K, V> {
+  entries: Vec<(Interval<K>, V)>,
+  /// `reach[i]` is the index of the entry with the greatest end among
+  /// `entries[..=i]`.
+  reach: Vec<usize>,
+}
+
+impl<K, V> Default for IntervalMap<K, V> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for IntervalMap<K, V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<K, V> IntervalMap<K, V> {
+  /// Creates an empty map.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      entries: Vec::new(),
+      reach: Vec::new(),
+    }
+  }
+
+  /// Creates an empty map with space for at least `capacity` entries.
+  #[inline]
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      entries: Vec::with_capacity(capacity),
+      reach: Vec::with_capacity(capacity),
+    }
+  }
+
+  /// Returns the number of entries in the map.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  /// Returns `true` if the map contains no entries.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  /// Removes all the entries of the map.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.entries.clear();
+    self.reach.clear();
+  }
+
+  /// Returns an iterator over the entries of the map, ordered by interval.
+  #[inline]
+  pub fn iter(&self) -> Iter<'_, K, V> {
+    Iter {
+      inner: self.entries.iter(),
+    }
+  }
+
+  /// Returns the entry with an interval equivalent to `interval`, i.e. with
+  /// the same kinds of bounds and equivalent endpoints.
+  #[inline]
+  pub fn get_key_value<Q, R>(&self, interval: &R) -> Option<(&Interval<K>, &V)>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + RangeBounds<Q>,
+  {
+    let index = self.search(interval).ok()?;
+    let (interval, value) = &self.entries[index];
+    Some((interval, value))
+  }
+
+  /// Returns a reference to the value of the interval equivalent to
+  /// `interval`.
+  #[inline]
+  pub fn get<Q, R>(&self, interval: &R) -> Option<&V>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + RangeBounds<Q>,
+  {
+    self.get_key_value(interval).map(|(_, v)| v)
+  }
+
+  /// Returns a mutable reference to the value of the interval equivalent to
+  /// `interval`.
+  #[inline]
+  pub fn get_mut<Q, R>(&mut self, interval: &R) -> Option<&mut V>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + RangeBounds<Q>,
+  {
+    let index = self.search(interval).ok()?;
+    Some(&mut self.entries[index].1)
+  }
+
+  /// Returns an iterator over the entries whose intervals contain `point`,
+  /// ordered by interval.
+  #[inline]
+  pub fn stab<'q, Q>(&self, point: &'q Q) -> Overlapping<'_, 'q, K, V, Q>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.overlapping_bounds((Bound::Included(point), Bound::Included(point)))
+  }
+
+  /// Returns an iterator over the entries whose intervals overlap `range`,
+  /// i.e. have at least one item in common with it, ordered by interval.
+  ///
+  /// An empty `range`, e.g. `(Excluded(8), Included(5))`, overlaps nothing.
+  #[inline]
+  pub fn overlapping<'r, Q, R>(&self, range: &'r R) -> Overlapping<'_, 'r, K, V, Q>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized + Comparable<Q>,
+    R: ?Sized + RangeBounds<Q>,
+  {
+    let bounds = (range.start_bound(), range.end_bound());
+    if ComparableRangeBounds::<Q>::compare_is_empty(range) {
+      return Overlapping {
+        inner: self.entries[..0].iter(),
+        range: bounds,
+      };
+    }
+    self.overlapping_bounds(bounds)
+  }
+
+  /// Inserts an interval with its value into the map, returning the previous
+  /// value of the interval, if any.
+  ///
+  /// An empty interval, e.g. `5..5`, contains no point, so it is ignored like
+  /// in [`RangeSet::insert`](crate::RangeSet::insert), and `None` is returned.
+  pub fn insert<I>(&mut self, interval: I, value: V) -> Option<V>
+  where
+    K: Ord,
+    I: Into<Interval<K>>,
+  {
+    let interval = interval.into();
+    if ComparableRangeBounds::<K>::compare_is_empty(&interval) {
+      return None;
+    }
+
+    match self.search(&interval) {
+      Ok(index) => Some(mem::replace(&mut self.entries[index].1, value)),
+      Err(index) => {
+        self.entries.insert(index, (interval, value));
+        self.update_reach(index);
+        None
+      }
+    }
+  }
+
+  /// Removes the entry with an interval equivalent to `interval`, returning
+  /// its value.
+  #[inline]
+  pub fn remove<Q, R>(&mut self, interval: &R) -> Option<V>
+  where
+    K: Ord + Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + RangeBounds<Q>,
+  {
+    self.remove_entry(interval).map(|(_, v)| v)
+  }
+
+  /// Removes the entry with an interval equivalent to `interval`, returning
+  /// it.
+  pub fn remove_entry<Q, R>(&mut self, interval: &R) -> Option<(Interval<K>, V)>
+  where
+    K: Ord + Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + RangeBounds<Q>,
+  {
+    let index = self.search(interval).ok()?;
+    let entry = self.entries.remove(index);
+    self.update_reach(index);
+    Some(entry)
+  }
+
+  /// Retains only the entries for which `f` returns `true`.
+  pub fn retain<F>(&mut self, mut f: F)
+  where
+    K: Ord,
+    F: FnMut(&Interval<K>, &mut V) -> bool,
+  {
+    self
+      .entries
+      .retain_mut(|(interval, value)| f(interval, value));
+    self.update_reach(0);
+  }
+
+  fn search<Q, R>(&self, interval: &R) -> Result<usize, usize>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + RangeBounds<Q>,
+  {
+    let cmp = |k: &K, q: &Q| k.compare(q);
+    self.entries.binary_search_by(|(i, _)| {
+      cmp_lower(i.start_bound(), interval.start_bound(), cmp)
+        .then_with(|| cmp_upper(i.end_bound(), interval.end_bound(), cmp))
+    })
+  }
+
+  fn overlapping_bounds<'r, Q>(
+    &self,
+    range: (Bound<&'r Q>, Bound<&'r Q>),
+  ) -> Overlapping<'_, 'r, K, V, Q>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    // The entries from `end` on start after the range.
+    let end = self.entries.partition_point(|(i, _)| {
+      lower_reaches_upper(i.start_bound(), range.1, |k: &K, q: &Q| k.compare(q))
+    });
+    // The entries before `start` end before the range, as none of them reaches
+    // further than `entries[reach[start - 1]]`.
+    let start = self.reach[..end].partition_point(|&r| {
+      !lower_reaches_upper(range.0, self.entries[r].0.end_bound(), |q: &Q, k: &K| {
+        k.compare(q).reverse()
+      })
+    });
+    Overlapping {
+      inner: self.entries[start..end].iter(),
+      range,
+    }
+  }
+
+  /// Recomputes `reach` from `index` on.
+  fn update_reach(&mut self, index: usize)
+  where
+    K: Ord,
+  {
+    self.reach.truncate(index);
+    for i in index..self.entries.len() {
+      let reach = match self.reach.last() {
+        Some(&prev)
+          if cmp_upper(
+            self.entries[prev].0.end_bound(),
+            self.entries[i].0.end_bound(),
+            Ord::cmp,
+          ) != Ordering::Less =>
+        {
+          prev
+        }
+        _ => i,
+      };
+      self.reach.push(reach);
+    }
+  }
+}
+
+impl<K: Ord, V, I: Into<Interval<K>>> FromIterator<(I, V)> for IntervalMap<K, V> {
+  fn from_iter<T: IntoIterator<Item = (I, V)>>(iter: T) -> Self {
+    let mut map = Self::new();
+    map.extend(iter);
+    map
+  }
+}
+
+impl<K: Ord, V, I: Into<Interval<K>>> Extend<(I, V)> for IntervalMap<K, V> {
+  fn extend<T: IntoIterator<Item = (I, V)>>(&mut self, iter: T) {
+    for (i, v) in iter {
+      self.insert(i, v);
+    }
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a IntervalMap<K, V> {
+  type Item = (&'a Interval<K>, &'a V);
+  type IntoIter = Iter<'a, K, V>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<K, V> IntoIterator for IntervalMap<K, V> {
+  type Item = (Interval<K>, V);
+  type IntoIter = vec::IntoIter<(Interval<K>, V)>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.entries.into_iter()
+  }
+}
+
+/// An iterator over the entries of an [`IntervalMap`], ordered by interval.
+#[derive(Debug)]
+pub struct Iter<'a, K, V> {
+  inner: slice::Iter<'a, (Interval<K>, V)>,
+}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+    }
+  }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+  type Item = (&'a Interval<K>, &'a V);
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.inner.next().map(|(i, v)| (i, v))
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.inner.size_hint()
+  }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.inner.next_back().map(|(i, v)| (i, v))
+  }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the entries of an [`IntervalMap`] which overlap a range,
+/// ordered by interval.
+pub struct Overlapping<'a, 'r, K, V, Q: ?Sized> {
+  inner: slice::Iter<'a, (Interval<K>, V)>,
+  range: (Bound<&'r Q>, Bound<&'r Q>),
+}
+
+impl<K, V, Q: ?Sized> Clone for Overlapping<'_, '_, K, V, Q> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+      range: self.range,
+    }
+  }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, Q: ?Sized + fmt::Debug> fmt::Debug
+  for Overlapping<'_, '_, K, V, Q>
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Overlapping")
+      .field("range", &self.range)
+      .finish()
+  }
+}
+
+/// Returns `true` if `interval` and `range` have at least one item in common.
+///
+/// Unlike [`ComparableRangeBounds::compare_overlaps`], this does not check
+/// whether either is empty: the intervals of a map never are, and
+/// [`IntervalMap::overlapping`] does not search for an empty range.
+#[inline]
+fn overlaps<K, Q>(interval: &Interval<K>, range: (Bound<&Q>, Bound<&Q>)) -> bool
+where
+  K: Comparable<Q>,
+  Q: ?Sized,
+{
+  lower_reaches_upper(interval.start_bound(), range.1, |k: &K, q: &Q| k.compare(q))
+    && lower_reaches_upper(range.0, interval.end_bound(), |q: &Q, k: &K| {
+      k.compare(q).reverse()
+    })
+}
+
+impl<'a, K, V, Q> Iterator for Overlapping<'a, '_, K, V, Q>
+where
+  K: Comparable<Q>,
+  Q: ?Sized,
+{
+  type Item = (&'a Interval<K>, &'a V);
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    let range = self.range;
+    self
+      .inner
+      .find(|(i, _)| overlaps(i, range))
+      .map(|(i, v)| (i, v))
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, self.inner.size_hint().1)
+  }
+}
+
+impl<K, V, Q> DoubleEndedIterator for Overlapping<'_, '_, K, V, Q>
+where
+  K: Comparable<Q>,
+  Q: ?Sized,
+{
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    let range = self.range;
+    self
+      .inner
+      .rfind(|(i, _)| overlaps(i, range))
+      .map(|(i, v)| (i, v))
+  }
+}
+
+impl<K, V, Q> FusedIterator for Overlapping<'_, '_, K, V, Q>
+where
+  K: Comparable<Q>,
+  Q: ?Sized,
+{
+}
+
+#[cfg(test)]
+mod tests {
+  use core::{cmp::Ordering, ops::Bound};
+  use std::vec::Vec;
+
+  use super::IntervalMap;
+  use crate::{
+    cmp_upper,
+    tests::{contains, members, points, ranges, xorshift, Range},
+  };
+
+  /// A deterministic sequence of indices into `ranges()`.
+  fn indices(len: usize) -> impl Iterator<Item = usize> {
+    xorshift().take(300).map(move |x| x as usize % len)
+  }
+
+  fn check(map: &IntervalMap<i32, usize>, model: &[(Range, usize)]) {
+    assert_eq!(map.len(), model.len());
+    for (range, value) in model {
+      assert_eq!(map.get(range), Some(value), "{:?}", range);
+    }
+
+    // `reach[i]` is the entry with the greatest end among `entries[..=i]`.
+    assert_eq!(map.reach.len(), map.entries.len());
+    for (i, &reach) in map.reach.iter().enumerate() {
+      assert!(reach <= i);
+      let end = map.entries[reach].0.end.as_ref();
+      for (interval, _) in &map.entries[..=i] {
+        assert_ne!(
+          cmp_upper(end, interval.end.as_ref(), Ord::cmp),
+          Ordering::Less
+        );
+      }
+    }
+
+    for point in points() {
+      let expected: Vec<_> = map
+        .iter()
+        .filter(|(i, _)| contains(&(i.start, i.end), point))
+        .collect();
+      assert_eq!(map.stab(&point).collect::<Vec<_>>(), expected, "{}", point);
+    }
+    for range in ranges() {
+      let points = members(&range);
+      let expected: Vec<_> = map
+        .iter()
+        .filter(|(i, _)| points.iter().any(|&p| contains(&(i.start, i.end), p)))
+        .collect();
+      let entries: Vec<_> = map.overlapping(&range).collect();
+      assert_eq!(entries, expected, "{:?}", range);
+      let mut reversed: Vec<_> = map.overlapping(&range).rev().collect();
+      reversed.reverse();
+      assert_eq!(reversed, expected, "{:?}", range);
+    }
+  }
+
+  #[test]
+  fn matches_model() {
+    let ranges = ranges();
+    let mut map = IntervalMap::new();
+    let mut model: Vec<(Range, usize)> = Vec::new();
+    check(&map, &model);
+    for (i, index) in indices(ranges.len()).enumerate() {
+      let range = ranges[index];
+      let position = model.iter().position(|(r, _)| *r == range);
+      if i % 3 == 2 {
+        let expected = position.map(|p| model.remove(p).1);
+        assert_eq!(map.remove(&range), expected, "{:?}", range);
+      } else if members(&range).is_empty() {
+        assert_eq!(map.insert(range, i), None, "{:?}", range);
+      } else {
+        let expected = match position {
+          Some(p) => Some(core::mem::replace(&mut model[p].1, i)),
+          None => {
+            model.push((range, i));
+            None
+          }
+        };
+        assert_eq!(map.insert(range, i), expected, "{:?}", range);
+      }
+      check(&map, &model);
+
+      if i % 50 == 49 {
+        map.retain(|_, v| {
+          *v += 1;
+          *v % 2 == 0
+        });
+        model.retain_mut(|(_, v)| {
+          *v += 1;
+          *v % 2 == 0
+        });
+        check(&map, &model);
+      }
+    }
+  }
+
+  #[test]
+  fn empty_intervals_are_ignored() {
+    let mut map = IntervalMap::new();
+    assert_eq!(map.insert(5..5, 'a'), None);
+    assert_eq!(
+      map.insert((Bound::Excluded(5), Bound::Included(5)), 'b'),
+      None
+    );
+    assert_eq!(
+      map.insert((Bound::Included(8), Bound::Excluded(2)), 'c'),
+      None
+    );
+    assert!(map.is_empty());
+    assert_eq!(map.get(&(5..5)), None);
+  }
+
+  #[test]
+  fn empty_queries_overlap_nothing() {
+    let mut map = IntervalMap::new();
+    map.insert(0..10, ());
+    map.insert(.., ());
+    let inverted = (Bound::Excluded(8), Bound::Included(5));
+    assert_eq!(map.overlapping(&inverted).next(), None);
+    assert_eq!(map.overlapping(&(3..3)).next_back(), None);
+    assert_eq!(map.overlapping(&(3..=3)).count(), 2);
+  }
+
+  #[test]
+  fn remove_and_retain_update_reach() {
+    let mut map: IntervalMap<i32, char> =
+      Vec::from([(0..100, 'a'), (1..2, 'b'), (3..4, 'c'), (5..50, 'd')])
+        .into_iter()
+        .collect();
+    assert!(map.stab(&60).map(|(_, &v)| v).eq(['a']));
+
+    // The long interval no longer covers the later ones.
+    assert_eq!(map.remove(&(0..100)), Some('a'));
+    assert_eq!(map.stab(&60).next(), None);
+    assert!(map.stab(&10).map(|(_, &v)| v).eq(['d']));
+    assert!(map.overlapping(&(0..4)).map(|(_, &v)| v).eq(['b', 'c']));
+
+    map.retain(|i, _| i.start != Bound::Included(5));
+    assert_eq!(map.stab(&10).next(), None);
+    assert!(map.iter().map(|(_, &v)| v).eq(['b', 'c']));
+    assert_eq!(map.reach, [0, 1]);
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.stab(&1).next(), None);
+  }
+}
@@ -0,0 +1,500 @@
+//! Contract checks for hand-written [`Equivalent`](crate::Equivalent),
+//! [`Comparable`] and [`EquivalentHash`] implementations.
+//!
+//! Every check takes sample values of `K` and `Q`, exercises the implementations
+//! on every combination of them, and reports the first pair (or triple) which
+//! violates the contract. They are meant to be called from the tests of crates
+//! that implement these traits by hand.
+
+use core::{
+  cmp::Ordering,
+  hash::{BuildHasher, Hash, Hasher},
+};
+
+use super::{Comparable, EquivalentHash};
+
+/// A violation of the contracts of [`Equivalent`](crate::Equivalent),
+/// [`Comparable`] or [`EquivalentHash`], found by one of the checks in this
+/// module.
+#[derive(Debug)]
+pub enum Violation<'a, K, Q> {
+  /// `key.compare(query)` returned `Ordering::Equal` while
+  /// `key.equivalent(query)` returned `false`, or the other way around.
+  CompareEquivalentMismatch {
+    /// The key sample.
+    key: &'a K,
+    /// The query sample.
+    query: &'a Q,
+    /// The result of `key.equivalent(query)`.
+    equivalent: bool,
+    /// The result of `key.compare(query)`.
+    ordering: Ordering,
+  },
+  /// `left.compare(right)` is not the reverse of `right.compare(left)`.
+  Asymmetric {
+    /// The left key sample.
+    left: &'a K,
+    /// The right key sample.
+    right: &'a K,
+    /// The result of `left.compare(right)`.
+    ordering: Ordering,
+    /// The result of `right.compare(left)`.
+    reversed: Ordering,
+  },
+  /// The ordering of `first`, `second` and `third` is not transitive, e.g.
+  /// `first < second` and `second < third`, but `first >= third`.
+  Intransitive {
+    /// The first key sample.
+    first: &'a K,
+    /// The second key sample.
+    second: &'a K,
+    /// The third key sample.
+    third: &'a K,
+  },
+  /// The ordering of `left` and `right` is inconsistent with how they compare
+  /// to `query`, e.g. `left < right`, but `left >= query` and `right <= query`.
+  Inconsistent {
+    /// The left key sample.
+    left: &'a K,
+    /// The right key sample.
+    right: &'a K,
+    /// The query sample.
+    query: &'a Q,
+  },
+  /// `key` is equivalent to `query`, but `key.hash_like(..)` differs from
+  /// `query.hash(..)`.
+  HashMismatch {
+    /// The key sample.
+    key: &'a K,
+    /// The query sample.
+    query: &'a Q,
+  },
+}
+
+// The samples are held by reference, so a violation can be copied whatever
+// `K` and `Q` are, which the derives would not allow.
+impl<K, Q> Clone for Violation<'_, K, Q> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<K, Q> Copy for Violation<'_, K, Q> {}
+
+impl<K: PartialEq, Q: PartialEq> PartialEq for Violation<'_, K, Q> {
+  fn eq(&self, other: &Self) -> bool {
+    use self::Violation::*;
+
+    match (*self, *other) {
+      (
+        CompareEquivalentMismatch {
+          key,
+          query,
+          equivalent,
+          ordering,
+        },
+        CompareEquivalentMismatch {
+          key: k,
+          query: q,
+          equivalent: e,
+          ordering: o,
+        },
+      ) => key == k && query == q && equivalent == e && ordering == o,
+      (
+        Asymmetric {
+          left,
+          right,
+          ordering,
+          reversed,
+        },
+        Asymmetric {
+          left: l,
+          right: r,
+          ordering: o,
+          reversed: rev,
+        },
+      ) => left == l && right == r && ordering == o && reversed == rev,
+      (
+        Intransitive {
+          first,
+          second,
+          third,
+        },
+        Intransitive {
+          first: f,
+          second: s,
+          third: t,
+        },
+      ) => first == f && second == s && third == t,
+      (
+        Inconsistent { left, right, query },
+        Inconsistent {
+          left: l,
+          right: r,
+          query: q,
+        },
+      ) => left == l && right == r && query == q,
+      (HashMismatch { key, query }, HashMismatch { key: k, query: q }) => key == k && query == q,
+      _ => false,
+    }
+  }
+}
+
+impl<K: Eq, Q: Eq> Eq for Violation<'_, K, Q> {}
+
+/// Checks that `key.compare(query)` returns `Ordering::Equal` exactly when
+/// `key.equivalent(query)` returns `true`, for every pair of samples.
+pub fn check_compare_equivalent<'a, K, Q>(
+  keys: &'a [K],
+  queries: &'a [Q],
+) -> Result<(), Violation<'a, K, Q>>
+where
+  K: Comparable<Q>,
+{
+  for key in keys {
+    for query in queries {
+      let equivalent = key.equivalent(query);
+      let ordering = key.compare(query);
+      if equivalent != (ordering == Ordering::Equal) {
+        return Err(Violation::CompareEquivalentMismatch {
+          key,
+          query,
+          equivalent,
+          ordering,
+        });
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Checks that the ordering of keys among themselves is antisymmetric and
+/// transitive, and that it is consistent with how the keys compare to every
+/// query sample.
+///
+/// For every `left`, `right` and `query`:
+///
+/// - `left.compare(right)` must be the reverse of `right.compare(left)`.
+/// - if `left == right`, both must compare the same to `query`.
+/// - if `left < right`, then `left < query` or `right > query`, i.e. `query`
+///   cannot sit below `left` and above `right` at the same time.
+///
+/// And for every `first`, `second` and `third` key, if `first <= second` and
+/// `second <= third`, then `first <= third`, where `first == third` only if
+/// both are equalities, and likewise for `>=`. This part takes cubic time in
+/// the number of keys.
+pub fn check_ordering<'a, K, Q>(keys: &'a [K], queries: &'a [Q]) -> Result<(), Violation<'a, K, Q>>
+where
+  K: Comparable<K> + Comparable<Q>,
+{
+  for left in keys {
+    for right in keys {
+      let ordering = Comparable::<K>::compare(left, right);
+      let reversed = Comparable::<K>::compare(right, left);
+      if ordering != reversed.reverse() {
+        return Err(Violation::Asymmetric {
+          left,
+          right,
+          ordering,
+          reversed,
+        });
+      }
+
+      for query in queries {
+        let l = Comparable::<Q>::compare(left, query);
+        let r = Comparable::<Q>::compare(right, query);
+        let consistent = match ordering {
+          Ordering::Equal => l == r,
+          Ordering::Less => l == Ordering::Less || r == Ordering::Greater,
+          Ordering::Greater => l == Ordering::Greater || r == Ordering::Less,
+        };
+
+        if !consistent {
+          return Err(Violation::Inconsistent { left, right, query });
+        }
+      }
+    }
+  }
+
+  for first in keys {
+    for second in keys {
+      let ab = Comparable::<K>::compare(first, second);
+      for third in keys {
+        let bc = Comparable::<K>::compare(second, third);
+        let expected = match (ab, bc) {
+          (Ordering::Equal, bc) => bc,
+          (ab, Ordering::Equal) => ab,
+          (ab, bc) if ab == bc => ab,
+          // `first < second > third` and the reverse say nothing about
+          // `first` and `third`.
+          _ => continue,
+        };
+
+        if Comparable::<K>::compare(first, third) != expected {
+          return Err(Violation::Intransitive {
+            first,
+            second,
+            third,
+          });
+        }
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Checks that `key.hash_like(..)` and `query.hash(..)` produce the same hash
+/// whenever `key.equivalent(query)` returns `true`, using hashers built by
+/// `build_hasher`.
+pub fn check_hash<'a, K, Q, S>(
+  keys: &'a [K],
+  queries: &'a [Q],
+  build_hasher: &S,
+) -> Result<(), Violation<'a, K, Q>>
+where
+  K: EquivalentHash<Q>,
+  Q: Hash,
+  S: BuildHasher,
+{
+  for key in keys {
+    for query in queries {
+      if !key.equivalent(query) {
+        continue;
+      }
+
+      let mut key_hasher = build_hasher.build_hasher();
+      key.hash_like(&mut key_hasher);
+      let mut query_hasher = build_hasher.build_hasher();
+      query.hash(&mut query_hasher);
+
+      if key_hasher.finish() != query_hasher.finish() {
+        return Err(Violation::HashMismatch { key, query });
+      }
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use core::{cmp::Ordering, hash::Hasher};
+  use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+  use super::*;
+  use crate::Equivalent;
+
+  type Build = BuildHasherDefault<DefaultHasher>;
+
+  /// Equivalent to every query, but compares by value.
+  #[derive(Debug, PartialEq)]
+  struct Loose(u8);
+
+  impl Equivalent<u8> for Loose {
+    fn equivalent(&self, _: &u8) -> bool {
+      true
+    }
+  }
+
+  impl Comparable<u8> for Loose {
+    fn compare(&self, key: &u8) -> Ordering {
+      self.0.cmp(key)
+    }
+  }
+
+  /// Claims to be below everything, itself included.
+  #[derive(Debug, PartialEq)]
+  struct Below(u8);
+
+  impl Equivalent<Below> for Below {
+    fn equivalent(&self, _: &Below) -> bool {
+      false
+    }
+  }
+
+  impl Comparable<Below> for Below {
+    fn compare(&self, _: &Below) -> Ordering {
+      Ordering::Less
+    }
+  }
+
+  impl Equivalent<u8> for Below {
+    fn equivalent(&self, key: &u8) -> bool {
+      self.0 == *key
+    }
+  }
+
+  impl Comparable<u8> for Below {
+    fn compare(&self, key: &u8) -> Ordering {
+      self.0.cmp(key)
+    }
+  }
+
+  /// Orders keys by value, but queries in reverse.
+  #[derive(Debug, PartialEq)]
+  struct Reversed(u8);
+
+  impl Equivalent<Reversed> for Reversed {
+    fn equivalent(&self, key: &Reversed) -> bool {
+      self.0 == key.0
+    }
+  }
+
+  impl Comparable<Reversed> for Reversed {
+    fn compare(&self, key: &Reversed) -> Ordering {
+      self.0.cmp(&key.0)
+    }
+  }
+
+  impl Equivalent<u8> for Reversed {
+    fn equivalent(&self, key: &u8) -> bool {
+      self.0 == *key
+    }
+  }
+
+  impl Comparable<u8> for Reversed {
+    fn compare(&self, key: &u8) -> Ordering {
+      key.cmp(&self.0)
+    }
+  }
+
+  /// Each hand beats the next one, and loses to the previous one.
+  #[derive(Debug, PartialEq)]
+  enum Hand {
+    Rock,
+    Paper,
+    Scissors,
+  }
+
+  impl Equivalent<Hand> for Hand {
+    fn equivalent(&self, key: &Hand) -> bool {
+      self == key
+    }
+  }
+
+  impl Comparable<Hand> for Hand {
+    fn compare(&self, key: &Hand) -> Ordering {
+      use self::Hand::*;
+
+      match (self, key) {
+        (Rock, Paper) | (Paper, Scissors) | (Scissors, Rock) => Ordering::Less,
+        (Paper, Rock) | (Scissors, Paper) | (Rock, Scissors) => Ordering::Greater,
+        _ => Ordering::Equal,
+      }
+    }
+  }
+
+  impl Equivalent<u8> for Hand {
+    fn equivalent(&self, _: &u8) -> bool {
+      false
+    }
+  }
+
+  impl Comparable<u8> for Hand {
+    fn compare(&self, _: &u8) -> Ordering {
+      Ordering::Less
+    }
+  }
+
+  /// Equivalent to the query of the same value, but hashes another one.
+  #[derive(Debug, PartialEq)]
+  struct Salted(u8);
+
+  impl Equivalent<u8> for Salted {
+    fn equivalent(&self, key: &u8) -> bool {
+      self.0 == *key
+    }
+  }
+
+  impl EquivalentHash<u8> for Salted {
+    fn hash_like<H: Hasher>(&self, state: &mut H) {
+      state.write_u8(self.0.wrapping_add(1));
+    }
+  }
+
+  #[test]
+  fn lawful() {
+    let keys = [1u32, 2, 2, 5];
+    let queries = [0u32, 2, 3, 9];
+    assert_eq!(check_compare_equivalent(&keys, &queries), Ok(()));
+    assert_eq!(check_ordering(&keys, &queries), Ok(()));
+    assert_eq!(check_hash(&keys, &queries, &Build::default()), Ok(()));
+  }
+
+  #[test]
+  fn compare_equivalent_mismatch() {
+    let keys = [Loose(1)];
+    let queries = [1u8, 2];
+    assert_eq!(
+      check_compare_equivalent(&keys, &queries),
+      Err(Violation::CompareEquivalentMismatch {
+        key: &keys[0],
+        query: &queries[1],
+        equivalent: true,
+        ordering: Ordering::Less,
+      })
+    );
+  }
+
+  #[test]
+  fn asymmetric() {
+    let keys = [Below(1)];
+    assert_eq!(
+      check_ordering::<_, u8>(&keys, &[]),
+      Err(Violation::Asymmetric {
+        left: &keys[0],
+        right: &keys[0],
+        ordering: Ordering::Less,
+        reversed: Ordering::Less,
+      })
+    );
+  }
+
+  #[test]
+  fn inconsistent() {
+    let keys = [Reversed(0), Reversed(2)];
+    let queries = [1u8];
+    assert_eq!(
+      check_ordering(&keys, &queries),
+      Err(Violation::Inconsistent {
+        left: &keys[0],
+        right: &keys[1],
+        query: &queries[0],
+      })
+    );
+  }
+
+  #[test]
+  fn intransitive() {
+    let keys = [Hand::Rock, Hand::Paper, Hand::Scissors];
+    assert_eq!(
+      check_ordering::<_, u8>(&keys, &[]),
+      Err(Violation::Intransitive {
+        first: &keys[0],
+        second: &keys[1],
+        third: &keys[2],
+      })
+    );
+  }
+
+  #[test]
+  fn hash_mismatch() {
+    let keys = [Salted(1), Salted(2)];
+    let queries = [2u8];
+    let violation = check_hash(&keys, &queries, &Build::default()).unwrap_err();
+    assert_eq!(
+      violation,
+      Violation::HashMismatch {
+        key: &keys[1],
+        query: &queries[0],
+      }
+    );
+
+    // Violations are `Copy` even though `Salted` is not.
+    let copy = violation;
+    assert_eq!(copy, violation);
+  }
+}
@@ -8,10 +8,147 @@
 #![allow(rustdoc::bare_urls)]
 #![deny(missing_docs)]
 
-#[cfg(test)]
+#[cfg(any(feature = "std", test))]
 extern crate std;
 
-use core::{borrow::Borrow, cmp::Ordering};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "derive")]
+extern crate equivalent_flipped_derive;
+
+#[cfg(feature = "equivalent")]
+extern crate equivalent;
+
+#[cfg(all(test, feature = "equivalent"))]
+extern crate hashbrown;
+
+/// Defines a `#[repr(transparent)]` wrapper around a possibly unsized value,
+/// with a `from_ref` constructor which wraps a reference to the value.
+///
+/// This is the only place where a reference is cast to a wrapper, so that the
+/// cast is written, and its safety checked, once.
+macro_rules! transparent_wrapper {
+  (
+    $(#[$attr:meta])*
+    pub struct $name:ident<$param:ident: ?Sized>(pub $field:ident);
+  ) => {
+    $(#[$attr])*
+    #[repr(transparent)]
+    pub struct $name<$param: ?Sized>(pub $field);
+
+    impl<$param: ?Sized> $name<$param> {
+      /// Wraps a reference to the value without copying it.
+      #[inline]
+      pub fn from_ref(value: &$field) -> &Self {
+        // Safety: the wrapper is `#[repr(transparent)]` over its only field, so
+        // both have the same layout.
+        unsafe { &*(value as *const $field as *const Self) }
+      }
+    }
+  };
+}
+
+pub use ascii::AsciiCaseInsensitive;
+mod ascii;
+
+pub use comparator::{ByComparable, Comparator, WithComparator};
+mod comparator;
+
+pub use composite::Composite;
+mod composite;
+
+pub use descending::Descending;
+mod descending;
+
+pub use float::{TotalF32, TotalF64};
+mod float;
+
+pub use interval::Interval;
+mod interval;
+
+pub use natural::Natural;
+mod natural;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use path::{OsStrLike, PathLike};
+#[cfg(feature = "std")]
+mod path;
+
+pub use prefix::{Prefix, PrefixBound};
+mod prefix;
+
+pub use project::Project;
+mod project;
+
+pub use sequence::Sequence;
+mod sequence;
+
+pub use slice::SliceExt;
+mod slice;
+
+pub use then::{Then, Wildcard};
+mod then;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use hash_table::HashTable;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use interval_map::IntervalMap;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use range_set::RangeSet;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use skip_list::SkipList;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use sorted_vec::{SortedVecMap, SortedVecSet};
+
+/// Derive macros for [`Equivalent`] and [`Comparable`].
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use equivalent_flipped_derive::{Comparable, Equivalent};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod hash_table;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod interval_map;
+
+#[cfg(feature = "laws")]
+#[cfg_attr(docsrs, doc(cfg(feature = "laws")))]
+pub mod laws;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod range_set;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod skip_list;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod sorted_vec;
+
+#[cfg(feature = "equivalent")]
+#[cfg_attr(docsrs, doc(cfg(feature = "equivalent")))]
+pub mod upstream;
+
+use core::{
+  borrow::Borrow,
+  cmp::Ordering,
+  hash::{Hash, Hasher},
+};
 
 /// Key equivalence trait.
 ///
@@ -39,6 +176,34 @@ where
   }
 }
 
+/// Key hashing trait.
+///
+/// This trait is a companion of [`Equivalent`] which expresses its hashing
+/// contract in the type system. It has one blanket implementation that uses
+/// the regular solution with `Borrow` and `Eq + Hash`, just like `HashMap`
+/// does, so hash maps can require a single `K: EquivalentHash<Q>` bound to get
+/// both equality and a hash that agrees with `Q`.
+///
+/// # Contract
+///
+/// If `key.equivalent(query)` returns `true`, then `key.hash_like(state)`
+/// **must** feed `state` exactly the same data as `query.hash(state)`.
+pub trait EquivalentHash<Q: ?Sized + Hash>: Equivalent<Q> {
+  /// Feeds this value into the given [`Hasher`] as if it were a `Q`.
+  fn hash_like<H: Hasher>(&self, state: &mut H);
+}
+
+impl<K: ?Sized, Q: ?Sized> EquivalentHash<Q> for K
+where
+  K: Borrow<Q>,
+  Q: Eq + Hash,
+{
+  #[inline]
+  fn hash_like<H: Hasher>(&self, state: &mut H) {
+    Hash::hash(self.borrow(), state)
+  }
+}
+
 /// Key ordering trait.
 ///
 /// This trait allows ordered map lookup to be customized. It has one blanket
@@ -61,6 +226,29 @@ where
   }
 }
 
+/// Key partial ordering trait.
+///
+/// This trait is the counterpart of [`Comparable`] for keys which only have a
+/// partial order, e.g. floating-point numbers. It has one blanket
+/// implementation that uses the regular solution with `Borrow` and
+/// `PartialOrd`. Ordered maps which need a total order should wrap such keys,
+/// e.g. in [`TotalF64`], instead.
+pub trait PartialComparable<Q: ?Sized> {
+  /// Compare self to `key` and return their ordering, if there is one.
+  fn partial_compare(&self, key: &Q) -> Option<Ordering>;
+}
+
+impl<K: ?Sized, Q: ?Sized> PartialComparable<Q> for K
+where
+  K: Borrow<Q>,
+  Q: PartialOrd,
+{
+  #[inline]
+  fn partial_compare(&self, key: &Q) -> Option<Ordering> {
+    PartialOrd::partial_cmp(self.borrow(), key)
+  }
+}
+
 /// `ComparableRangeBounds` is implemented as an extention to `RangeBounds` to
 /// allow for comparison of items with range bounds.
 pub trait ComparableRangeBounds<Q: ?Sized>: core::ops::RangeBounds<Q> {
@@ -81,6 +269,217 @@ pub trait ComparableRangeBounds<Q: ?Sized>: core::ops::RangeBounds<Q> {
       Bound::Unbounded => true,
     })
   }
+
+  /// Returns where `item` is located relative to the range, in a single pass
+  /// over its bounds.
+  ///
+  /// `compare_position(item) == RangePosition::Inside` is equivalent to
+  /// `compare_contains(item)`, but it also tells range scans whether to skip
+  /// forward to the start of the range or to stop.
+  fn compare_position<K>(&self, item: &K) -> RangePosition
+  where
+    K: ?Sized + Comparable<Q>,
+  {
+    use core::ops::Bound;
+
+    let below = match self.start_bound() {
+      Bound::Included(start) => item.compare(start) == Ordering::Less,
+      Bound::Excluded(start) => item.compare(start) != Ordering::Greater,
+      Bound::Unbounded => false,
+    };
+    if below {
+      return RangePosition::Below;
+    }
+
+    let above = match self.end_bound() {
+      Bound::Included(end) => item.compare(end) == Ordering::Greater,
+      Bound::Excluded(end) => item.compare(end) != Ordering::Less,
+      Bound::Unbounded => false,
+    };
+    if above {
+      RangePosition::Above
+    } else {
+      RangePosition::Inside
+    }
+  }
+
+  /// Returns `true` if the range and `other` have at least one item in common.
+  ///
+  /// Like the other range-to-range relations, this treats ranges as intervals
+  /// of a dense order, e.g. `(Excluded(1), Excluded(2))` is not empty even if
+  /// there is no integer between `1` and `2`. An empty range, see
+  /// [`compare_is_empty`](ComparableRangeBounds::compare_is_empty), has no
+  /// items, so it overlaps no range, is contained in every range, and is both
+  /// before and after every range.
+  fn compare_overlaps<K, R>(&self, other: &R) -> bool
+  where
+    Q: Comparable<Q>,
+    K: ?Sized + Comparable<Q> + Comparable<K>,
+    R: ?Sized + core::ops::RangeBounds<K>,
+  {
+    !self.compare_is_before(other) && !self.compare_is_after(other)
+  }
+
+  /// Returns `true` if every item of `other` is contained in the range.
+  fn compare_contains_range<K, R>(&self, other: &R) -> bool
+  where
+    Q: Comparable<Q>,
+    K: ?Sized + Comparable<Q> + Comparable<K>,
+    R: ?Sized + core::ops::RangeBounds<K>,
+  {
+    // A range covering the bounds of a non-empty `other` is not empty either.
+    let cmp = |q: &Q, k: &K| k.compare(q).reverse();
+    ComparableRangeBounds::<K>::compare_is_empty(other)
+      || (lower_covers(self.start_bound(), other.start_bound(), cmp)
+        && upper_covers(self.end_bound(), other.end_bound(), cmp))
+  }
+
+  /// Returns `true` if every item of the range is less than every item of
+  /// `other`.
+  fn compare_is_before<K, R>(&self, other: &R) -> bool
+  where
+    Q: Comparable<Q>,
+    K: ?Sized + Comparable<Q> + Comparable<K>,
+    R: ?Sized + core::ops::RangeBounds<K>,
+  {
+    self.compare_is_empty()
+      || ComparableRangeBounds::<K>::compare_is_empty(other)
+      || !lower_reaches_upper(other.start_bound(), self.end_bound(), |k: &K, q: &Q| {
+        k.compare(q)
+      })
+  }
+
+  /// Returns `true` if every item of the range is greater than every item of
+  /// `other`.
+  fn compare_is_after<K, R>(&self, other: &R) -> bool
+  where
+    Q: Comparable<Q>,
+    K: ?Sized + Comparable<Q> + Comparable<K>,
+    R: ?Sized + core::ops::RangeBounds<K>,
+  {
+    self.compare_is_empty()
+      || ComparableRangeBounds::<K>::compare_is_empty(other)
+      || !lower_reaches_upper(self.start_bound(), other.end_bound(), |q: &Q, k: &K| {
+        k.compare(q).reverse()
+      })
+  }
+
+  /// Returns `true` if the range contains no items, i.e. its start is after its
+  /// end, or they are equal and at least one of them is excluded.
+  fn compare_is_empty(&self) -> bool
+  where
+    Q: Comparable<Q>,
+  {
+    !lower_reaches_upper(self.start_bound(), self.end_bound(), |a: &Q, b: &Q| {
+      a.compare(b)
+    })
+  }
+
+  /// Returns the bounds of the items contained in both the range and `other`,
+  /// or `None` if there are no such items.
+  fn compare_intersection<'a, R>(
+    &'a self,
+    other: &'a R,
+  ) -> Option<(core::ops::Bound<&'a Q>, core::ops::Bound<&'a Q>)>
+  where
+    Q: Comparable<Q>,
+    R: ?Sized + core::ops::RangeBounds<Q>,
+  {
+    let cmp = |a: &Q, b: &Q| a.compare(b);
+    let start = max_by(self.start_bound(), other.start_bound(), |a, b| {
+      cmp_lower(*a, *b, cmp)
+    });
+    let end = min_by(self.end_bound(), other.end_bound(), |a, b| {
+      cmp_upper(*a, *b, cmp)
+    });
+    if lower_reaches_upper(start, end, cmp) {
+      Some((start, end))
+    } else {
+      None
+    }
+  }
+
+  /// Returns the bounds of the items contained in either the range or `other`,
+  /// or `None` if they are separated by a gap, so their union is not a range.
+  ///
+  /// Ranges which only touch, e.g. `0..1` and `1..2`, have a contiguous union.
+  fn compare_union<'a, R>(
+    &'a self,
+    other: &'a R,
+  ) -> Option<(core::ops::Bound<&'a Q>, core::ops::Bound<&'a Q>)>
+  where
+    Q: Comparable<Q>,
+    R: ?Sized + core::ops::RangeBounds<Q>,
+  {
+    let cmp = |a: &Q, b: &Q| a.compare(b);
+    if self.compare_is_empty() {
+      return Some((other.start_bound(), other.end_bound()));
+    }
+    if other.compare_is_empty() {
+      return Some((self.start_bound(), self.end_bound()));
+    }
+    if !lower_touches_upper(self.start_bound(), other.end_bound(), cmp)
+      || !lower_touches_upper(other.start_bound(), self.end_bound(), cmp)
+    {
+      return None;
+    }
+
+    Some((
+      min_by(self.start_bound(), other.start_bound(), |a, b| {
+        cmp_lower(*a, *b, cmp)
+      }),
+      max_by(self.end_bound(), other.end_bound(), |a, b| {
+        cmp_upper(*a, *b, cmp)
+      }),
+    ))
+  }
+}
+
+/// `PartialComparableRangeBounds` is implemented as an extention to
+/// `RangeBounds` to allow for comparison of partially ordered items with range
+/// bounds.
+pub trait PartialComparableRangeBounds<Q: ?Sized>: core::ops::RangeBounds<Q> {
+  /// Returns `true` if `item` is contained in the range, i.e. `item` is
+  /// comparable to both bounds and lies between them.
+  fn partial_compare_contains<K>(&self, item: &K) -> bool
+  where
+    K: ?Sized + PartialComparable<Q>,
+  {
+    use core::ops::Bound;
+
+    (match self.start_bound() {
+      Bound::Included(start) => item
+        .partial_compare(start)
+        .map_or(false, |ord| ord != Ordering::Less),
+      Bound::Excluded(start) => item.partial_compare(start) == Some(Ordering::Greater),
+      Bound::Unbounded => true,
+    }) && (match self.end_bound() {
+      Bound::Included(end) => item
+        .partial_compare(end)
+        .map_or(false, |ord| ord != Ordering::Greater),
+      Bound::Excluded(end) => item.partial_compare(end) == Some(Ordering::Less),
+      Bound::Unbounded => true,
+    })
+  }
+}
+
+impl<R, Q> PartialComparableRangeBounds<Q> for R
+where
+  R: ?Sized + core::ops::RangeBounds<Q>,
+  Q: ?Sized,
+{
+}
+
+/// The position of an item relative to a range, as returned by
+/// [`ComparableRangeBounds::compare_position`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RangePosition {
+  /// The item is before the start of the range.
+  Below,
+  /// The item is contained in the range.
+  Inside,
+  /// The item is after the end of the range.
+  Above,
 }
 
 impl<R, Q> ComparableRangeBounds<Q> for R
@@ -89,3 +488,407 @@ where
   Q: ?Sized,
 {
 }
+
+/// Returns `true` if there are items which are both above the lower bound
+/// `lower` and below the upper bound `upper`, where `cmp` orders an `A` relative
+/// to a `B`.
+#[inline]
+fn lower_reaches_upper<A, B>(
+  lower: core::ops::Bound<&A>,
+  upper: core::ops::Bound<&B>,
+  cmp: impl FnOnce(&A, &B) -> Ordering,
+) -> bool
+where
+  A: ?Sized,
+  B: ?Sized,
+{
+  use core::ops::Bound;
+
+  match (lower, upper) {
+    (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+    (Bound::Included(l), Bound::Included(u)) => cmp(l, u) != Ordering::Greater,
+    (Bound::Included(l), Bound::Excluded(u))
+    | (Bound::Excluded(l), Bound::Included(u))
+    | (Bound::Excluded(l), Bound::Excluded(u)) => cmp(l, u) == Ordering::Less,
+  }
+}
+
+/// Returns `true` if the lower bound `lower` and the upper bound `upper` leave
+/// no gap between the items they admit, where `cmp` orders an `A` relative to a
+/// `B`.
+#[inline]
+fn lower_touches_upper<A, B>(
+  lower: core::ops::Bound<&A>,
+  upper: core::ops::Bound<&B>,
+  cmp: impl FnOnce(&A, &B) -> Ordering,
+) -> bool
+where
+  A: ?Sized,
+  B: ?Sized,
+{
+  use core::ops::Bound;
+
+  match (lower, upper) {
+    (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+    (Bound::Excluded(l), Bound::Excluded(u)) => cmp(l, u) == Ordering::Less,
+    (Bound::Included(l), Bound::Included(u))
+    | (Bound::Included(l), Bound::Excluded(u))
+    | (Bound::Excluded(l), Bound::Included(u)) => cmp(l, u) != Ordering::Greater,
+  }
+}
+
+/// Orders the lower bound `a` relative to the lower bound `b` by the first item
+/// they admit, where `cmp` orders an `A` relative to a `B`.
+#[inline]
+fn cmp_lower<A, B>(
+  a: core::ops::Bound<&A>,
+  b: core::ops::Bound<&B>,
+  cmp: impl FnOnce(&A, &B) -> Ordering,
+) -> Ordering
+where
+  A: ?Sized,
+  B: ?Sized,
+{
+  use core::ops::Bound;
+
+  match (a, b) {
+    (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+    (Bound::Unbounded, _) => Ordering::Less,
+    (_, Bound::Unbounded) => Ordering::Greater,
+    (Bound::Included(x), Bound::Included(y)) | (Bound::Excluded(x), Bound::Excluded(y)) => {
+      cmp(x, y)
+    }
+    (Bound::Included(x), Bound::Excluded(y)) => cmp(x, y).then(Ordering::Less),
+    (Bound::Excluded(x), Bound::Included(y)) => cmp(x, y).then(Ordering::Greater),
+  }
+}
+
+/// Orders the upper bound `a` relative to the upper bound `b` by the last item
+/// they admit, where `cmp` orders an `A` relative to a `B`.
+#[inline]
+fn cmp_upper<A, B>(
+  a: core::ops::Bound<&A>,
+  b: core::ops::Bound<&B>,
+  cmp: impl FnOnce(&A, &B) -> Ordering,
+) -> Ordering
+where
+  A: ?Sized,
+  B: ?Sized,
+{
+  use core::ops::Bound;
+
+  match (a, b) {
+    (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+    (Bound::Unbounded, _) => Ordering::Greater,
+    (_, Bound::Unbounded) => Ordering::Less,
+    (Bound::Included(x), Bound::Included(y)) | (Bound::Excluded(x), Bound::Excluded(y)) => {
+      cmp(x, y)
+    }
+    (Bound::Included(x), Bound::Excluded(y)) => cmp(x, y).then(Ordering::Greater),
+    (Bound::Excluded(x), Bound::Included(y)) => cmp(x, y).then(Ordering::Less),
+  }
+}
+
+/// Returns the greater of `a` and `b` according to `cmp`, preferring `a` if
+/// they are equal.
+#[inline]
+fn max_by<T>(a: T, b: T, cmp: impl FnOnce(&T, &T) -> Ordering) -> T {
+  match cmp(&a, &b) {
+    Ordering::Less => b,
+    _ => a,
+  }
+}
+
+/// Returns the lesser of `a` and `b` according to `cmp`, preferring `a` if
+/// they are equal.
+#[inline]
+fn min_by<T>(a: T, b: T, cmp: impl FnOnce(&T, &T) -> Ordering) -> T {
+  match cmp(&a, &b) {
+    Ordering::Greater => b,
+    _ => a,
+  }
+}
+
+/// Returns `true` if the lower bound `outer` admits every item admitted by the
+/// lower bound `inner`, where `cmp` orders an `A` relative to a `B`.
+#[inline]
+fn lower_covers<A, B>(
+  outer: core::ops::Bound<&A>,
+  inner: core::ops::Bound<&B>,
+  cmp: impl FnOnce(&A, &B) -> Ordering,
+) -> bool
+where
+  A: ?Sized,
+  B: ?Sized,
+{
+  use core::ops::Bound;
+
+  match (outer, inner) {
+    (Bound::Unbounded, _) => true,
+    (_, Bound::Unbounded) => false,
+    (Bound::Excluded(o), Bound::Included(i)) => cmp(o, i) == Ordering::Less,
+    (Bound::Included(o), Bound::Included(i))
+    | (Bound::Included(o), Bound::Excluded(i))
+    | (Bound::Excluded(o), Bound::Excluded(i)) => cmp(o, i) != Ordering::Greater,
+  }
+}
+
+/// Returns `true` if the upper bound `outer` admits every item admitted by the
+/// upper bound `inner`, where `cmp` orders an `A` relative to a `B`.
+#[inline]
+fn upper_covers<A, B>(
+  outer: core::ops::Bound<&A>,
+  inner: core::ops::Bound<&B>,
+  cmp: impl FnOnce(&A, &B) -> Ordering,
+) -> bool
+where
+  A: ?Sized,
+  B: ?Sized,
+{
+  use core::ops::Bound;
+
+  match (outer, inner) {
+    (Bound::Unbounded, _) => true,
+    (_, Bound::Unbounded) => false,
+    (Bound::Excluded(o), Bound::Included(i)) => cmp(o, i) == Ordering::Greater,
+    (Bound::Included(o), Bound::Included(i))
+    | (Bound::Included(o), Bound::Excluded(i))
+    | (Bound::Excluded(o), Bound::Excluded(i)) => cmp(o, i) != Ordering::Less,
+  }
+}
+
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+  use core::hash::{Hash, Hasher};
+
+  use super::EquivalentHash;
+
+  /// Calls `key.hash_like(state)`, with `Q` inferred from the type of the query
+  /// field returned by `field`.
+  #[inline]
+  pub fn hash_like<K, T, Q, H>(key: &K, _field: fn(&T) -> &Q, state: &mut H)
+  where
+    K: ?Sized + EquivalentHash<Q>,
+    T: ?Sized,
+    Q: ?Sized + Hash,
+    H: Hasher,
+  {
+    key.hash_like(state)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::{
+    hash::{Hash, Hasher},
+    ops::Bound,
+  };
+  use std::{collections::hash_map::DefaultHasher, string::String, vec::Vec};
+
+  use super::{ComparableRangeBounds, EquivalentHash, RangePosition};
+
+  /// Hashes `query` the way a hash map hashes its lookups.
+  pub(crate) fn hash<Q: ?Sized + Hash>(query: &Q) -> u64 {
+    let mut state = DefaultHasher::new();
+    query.hash(&mut state);
+    state.finish()
+  }
+
+  /// Hashes `key` the way a hash map hashes it for lookups with `Q`.
+  pub(crate) fn hash_like<K, Q>(key: &K) -> u64
+  where
+    K: ?Sized + EquivalentHash<Q>,
+    Q: ?Sized + Hash,
+  {
+    let mut state = DefaultHasher::new();
+    key.hash_like(&mut state);
+    state.finish()
+  }
+
+  /// A range over the test model, see [`points`].
+  pub(crate) type Range = (Bound<i32>, Bound<i32>);
+
+  /// Every bound at the even values `0..=6`, and `Unbounded`.
+  pub(crate) fn bounds() -> Vec<Bound<i32>> {
+    let mut bounds = Vec::from([Bound::Unbounded]);
+    for v in (0..=6).step_by(2) {
+      bounds.push(Bound::Included(v));
+      bounds.push(Bound::Excluded(v));
+    }
+    bounds
+  }
+
+  /// Every range made of two [`bounds`], empty and inverted ones included.
+  pub(crate) fn ranges() -> Vec<Range> {
+    let bounds = bounds();
+    let mut ranges = Vec::new();
+    for &start in &bounds {
+      for &end in &bounds {
+        ranges.push((start, end));
+      }
+    }
+    ranges
+  }
+
+  /// The sample points of the test model.
+  ///
+  /// Ranges are bounded by even values, so the odd points stand for the items
+  /// between two bounds of a dense order, e.g. `(Excluded(0), Excluded(2))`
+  /// contains `1`, and the points beyond the bounds stand for the items that
+  /// only unbounded ranges contain.
+  pub(crate) fn points() -> Vec<i32> {
+    (-2..=8).collect()
+  }
+
+  /// An endless, deterministic sequence of pseudo-random numbers, from a
+  /// xorshift generator, for the tests which replay random operations.
+  #[cfg(feature = "alloc")]
+  pub(crate) fn xorshift() -> impl Iterator<Item = u32> {
+    let mut state = 0x2545_f491u32;
+    core::iter::repeat_with(move || {
+      state ^= state << 13;
+      state ^= state >> 17;
+      state ^= state << 5;
+      state
+    })
+  }
+
+  /// Whether `range` contains `point`, written independently of the crate.
+  pub(crate) fn contains(range: &Range, point: i32) -> bool {
+    (match range.0 {
+      Bound::Included(s) => s <= point,
+      Bound::Excluded(s) => s < point,
+      Bound::Unbounded => true,
+    }) && (match range.1 {
+      Bound::Included(e) => point <= e,
+      Bound::Excluded(e) => point < e,
+      Bound::Unbounded => true,
+    })
+  }
+
+  /// The sample points contained in `range`.
+  pub(crate) fn members(range: &Range) -> Vec<i32> {
+    points()
+      .into_iter()
+      .filter(|&p| contains(range, p))
+      .collect()
+  }
+
+  /// Turns a pair of borrowed bounds into a [`Range`].
+  fn owned((start, end): (Bound<&i32>, Bound<&i32>)) -> Range {
+    (start.cloned(), end.cloned())
+  }
+
+  /// Whether the sample points `members` have no gap between them, i.e. they
+  /// are the members of a single range.
+  fn contiguous(members: &[i32]) -> bool {
+    members.windows(2).all(|w| w[1] == w[0] + 1)
+  }
+
+  #[test]
+  fn hash_like_matches_borrowed() {
+    let key = String::from("key");
+    assert_eq!(hash_like::<_, str>(&key), hash("key"));
+    assert_ne!(hash_like::<_, str>(&key), hash("kez"));
+
+    let key = Vec::from([1u32, 2, 3]);
+    assert_eq!(hash_like::<_, [u32]>(&key), hash(&[1u32, 2, 3][..]));
+    assert_ne!(hash_like::<_, [u32]>(&key), hash(&[1u32, 2][..]));
+  }
+
+  #[test]
+  fn contains_and_is_empty() {
+    for range in ranges() {
+      for p in points() {
+        assert_eq!(
+          range.compare_contains(&p),
+          contains(&range, p),
+          "{:?} {}",
+          range,
+          p
+        );
+      }
+      assert_eq!(
+        ComparableRangeBounds::<i32>::compare_is_empty(&range),
+        members(&range).is_empty(),
+        "{:?}",
+        range
+      );
+    }
+  }
+
+  #[test]
+  fn range_relations() {
+    let ranges = ranges();
+    for a in &ranges {
+      let ma = members(a);
+      for b in &ranges {
+        let mb = members(b);
+        let overlaps = ma.iter().any(|p| mb.contains(p));
+        let contains_range = mb.iter().all(|p| ma.contains(p));
+        let before = ma.iter().all(|p| mb.iter().all(|q| p < q));
+        let after = ma.iter().all(|p| mb.iter().all(|q| p > q));
+
+        let msg = (a, b);
+        assert_eq!(a.compare_overlaps(b), overlaps, "{:?}", msg);
+        assert_eq!(a.compare_contains_range(b), contains_range, "{:?}", msg);
+        assert_eq!(a.compare_is_before(b), before, "{:?}", msg);
+        assert_eq!(a.compare_is_after(b), after, "{:?}", msg);
+      }
+    }
+  }
+
+  #[test]
+  fn intersection_and_union() {
+    for a in &ranges() {
+      let ma = members(a);
+      for b in &ranges() {
+        let mb = members(b);
+        let msg = (a, b);
+
+        let both: Vec<_> = ma.iter().copied().filter(|p| mb.contains(p)).collect();
+        match a.compare_intersection(b) {
+          Some(i) => {
+            assert!(!both.is_empty(), "{:?}", msg);
+            assert_eq!(members(&owned(i)), both, "{:?}", msg);
+          }
+          None => assert!(both.is_empty(), "{:?}", msg),
+        }
+
+        let mut either: Vec<_> = ma.iter().chain(&mb).copied().collect();
+        either.sort_unstable();
+        either.dedup();
+        match a.compare_union(b) {
+          Some(u) => assert_eq!(members(&owned(u)), either, "{:?}", msg),
+          None => assert!(!contiguous(&either), "{:?}", msg),
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn position() {
+    for range in &ranges() {
+      let members = members(range);
+      for p in points() {
+        let expected = if contains(range, p) {
+          RangePosition::Inside
+        } else if members.first().map_or(false, |&first| p > first) {
+          RangePosition::Above
+        } else if members.is_empty() {
+          // There is nothing between an empty range's bounds, so every point is
+          // below its start or above its end.
+          match range.0 {
+            Bound::Included(s) if p >= s => RangePosition::Above,
+            Bound::Excluded(s) if p > s => RangePosition::Above,
+            _ => RangePosition::Below,
+          }
+        } else {
+          RangePosition::Below
+        };
+        assert_eq!(range.compare_position(&p), expected, "{:?} {}", range, p);
+      }
+    }
+  }
+}
@@ -0,0 +1,201 @@
+use core::{
+  borrow::Borrow,
+  cmp::Ordering,
+  hash::{Hash, Hasher},
+};
+
+transparent_wrapper! {
+  /// A key or query wrapper which orders strings naturally, i.e. runs of ASCII
+  /// digits are compared by their numeric value, so `"file9" < "file10"`.
+  ///
+  /// Store keys as `Natural<String>` (or any `T: AsRef<str>`) and look them up
+  /// with `Natural<str>`. The owned forms `Borrow` the unsized form, so the
+  /// blanket [`Equivalent`](crate::Equivalent),
+  /// [`Comparable`](crate::Comparable) and
+  /// [`EquivalentHash`](crate::EquivalentHash) implementations apply.
+  ///
+  /// Strings which only differ by leading zeros, e.g. `"v01"` and `"v1"`, are
+  /// ordered by their bytes, so the ordering is consistent with equality, which
+  /// is the plain string equality.
+  #[derive(Debug, Default, Clone, Copy)]
+  pub struct Natural<T: ?Sized>(pub T);
+}
+
+impl<T: ?Sized + AsRef<str>> PartialEq for Natural<T> {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.0.as_ref() == other.0.as_ref()
+  }
+}
+
+impl<T: ?Sized + AsRef<str>> Eq for Natural<T> {}
+
+impl<T: ?Sized + AsRef<str>> PartialOrd for Natural<T> {
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T: ?Sized + AsRef<str>> Ord for Natural<T> {
+  #[inline]
+  fn cmp(&self, other: &Self) -> Ordering {
+    let this = self.0.as_ref().as_bytes();
+    let other = other.0.as_ref().as_bytes();
+    natural_cmp(this, other).then_with(|| this.cmp(other))
+  }
+}
+
+impl<T: ?Sized + AsRef<str>> Hash for Natural<T> {
+  #[inline]
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.0.as_ref().hash(state)
+  }
+}
+
+impl<T: AsRef<str>> Borrow<Natural<str>> for Natural<T> {
+  #[inline]
+  fn borrow(&self) -> &Natural<str> {
+    Natural::from_ref(self.0.as_ref())
+  }
+}
+
+/// Compares `a` and `b` byte by byte, except that runs of ASCII digits are
+/// compared by their numeric value, ignoring leading zeros.
+fn natural_cmp(a: &[u8], b: &[u8]) -> Ordering {
+  let (mut i, mut j) = (0, 0);
+  while i < a.len() && j < b.len() {
+    if a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+      let (ai, aj) = digits(a, i);
+      let (bi, bj) = digits(b, j);
+      let (x, y) = (&a[ai..aj], &b[bi..bj]);
+      // Without leading zeros, a longer run of digits is a greater number.
+      match x.len().cmp(&y.len()).then_with(|| x.cmp(y)) {
+        Ordering::Equal => {}
+        ord => return ord,
+      }
+      i = aj;
+      j = bj;
+    } else {
+      match a[i].cmp(&b[j]) {
+        Ordering::Equal => {}
+        ord => return ord,
+      }
+      i += 1;
+      j += 1;
+    }
+  }
+
+  (a.len() - i).cmp(&(b.len() - j))
+}
+
+/// Returns the bounds of the significant digits of the run of digits starting
+/// at `start`, i.e. without its leading zeros.
+#[inline]
+fn digits(s: &[u8], start: usize) -> (usize, usize) {
+  let mut begin = start;
+  while begin < s.len() && s[begin] == b'0' {
+    begin += 1;
+  }
+  let mut end = begin;
+  while end < s.len() && s[end].is_ascii_digit() {
+    end += 1;
+  }
+  (begin, end)
+}
+
+#[cfg(test)]
+mod tests {
+  use core::cmp::Ordering;
+  use std::{string::String, vec::Vec};
+
+  use super::Natural;
+  use crate::{
+    tests::{hash, hash_like},
+    Comparable,
+  };
+
+  fn samples() -> Vec<&'static str> {
+    Vec::from([
+      "",
+      "0",
+      "00",
+      "1",
+      "01",
+      "001",
+      "2",
+      "9",
+      "10",
+      "010",
+      "a",
+      "a1",
+      "a01",
+      "a2",
+      "a10",
+      "a10b",
+      "a10b2",
+      "a10b10",
+      "b",
+      "file9.txt",
+      "file10.txt",
+      "v1.2",
+      "v1.10",
+      "99999999999999999999",
+      "100000000000000000000",
+    ])
+  }
+
+  #[test]
+  fn numeric_runs() {
+    let ord = |a: &str, b: &str| Natural::from_ref(a).cmp(Natural::from_ref(b));
+    assert_eq!(ord("file9", "file10"), Ordering::Less);
+    assert_eq!(ord("v1.10", "v1.2"), Ordering::Greater);
+    assert_eq!(ord("a10b2", "a10b10"), Ordering::Less);
+    assert_eq!(
+      ord("99999999999999999999", "100000000000000000000"),
+      Ordering::Less
+    );
+    assert_eq!(ord("x", "x1"), Ordering::Less);
+  }
+
+  #[test]
+  fn leading_zeros_fall_back_to_bytes() {
+    let ord = |a: &str, b: &str| Natural::from_ref(a).cmp(Natural::from_ref(b));
+    assert_eq!(ord("v01", "v1"), Ordering::Less);
+    assert_eq!(ord("v01", "v2"), Ordering::Less);
+    assert_ne!(Natural::from_ref("v01"), Natural::from_ref("v1"));
+  }
+
+  #[test]
+  fn total_order_consistent_with_eq() {
+    let samples = samples();
+    for &a in &samples {
+      for &b in &samples {
+        let ab = Natural::from_ref(a).cmp(Natural::from_ref(b));
+        assert_eq!(ab == Ordering::Equal, a == b, "{:?} {:?}", a, b);
+        assert_eq!(ab, Natural::from_ref(b).cmp(Natural::from_ref(a)).reverse());
+        for &c in &samples {
+          let bc = Natural::from_ref(b).cmp(Natural::from_ref(c));
+          if ab == bc {
+            assert_eq!(Natural::from_ref(a).cmp(Natural::from_ref(c)), ab);
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn owned_keys() {
+    let mut keys: Vec<Natural<String>> = ["file10", "file9", "file1"]
+      .iter()
+      .map(|s| Natural(String::from(*s)))
+      .collect();
+    keys.sort();
+    let sorted: Vec<&str> = keys.iter().map(|k| k.0.as_str()).collect();
+    assert_eq!(sorted, ["file1", "file9", "file10"]);
+
+    let query = Natural::from_ref("file9");
+    assert_eq!(keys[1].compare(query), Ordering::Equal);
+    assert_eq!(hash_like::<_, Natural<str>>(&keys[1]), hash(query));
+  }
+}
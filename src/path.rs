@@ -0,0 +1,201 @@
+use core::{
+  cmp::Ordering,
+  hash::{Hash, Hasher},
+};
+use std::{
+  ffi::{OsStr, OsString},
+  path::{Path, PathBuf},
+};
+
+#[cfg(unix)]
+use std::os::unix::ffi::OsStrExt;
+
+use super::{Comparable, Equivalent, EquivalentHash};
+
+transparent_wrapper! {
+  /// A query wrapper to look up `Path` and `PathBuf` keys with `str` or, on
+  /// Unix, byte string queries, without converting them.
+  ///
+  /// The keys are compared to the query the way `Path` compares, i.e. component
+  /// by component. So `"a//b"`, `"a/./b"` and `"a/b/"` are all equivalent to
+  /// the key `a/b`, and the keys are ordered by their components, which differs
+  /// from the byte order: the key `a/b` is less than `"a-b"`, although `'/'` is
+  /// greater than `'-'`. The query hashes like a `Path`, so these keys also
+  /// implement [`EquivalentHash`].
+  ///
+  /// `OsStr` and `OsString` keys compare and hash byte by byte instead, so they
+  /// are looked up with [`OsStrLike`].
+  #[derive(Debug, Default, Clone, Copy)]
+  pub struct PathLike<Q: ?Sized>(pub Q);
+}
+
+impl PathLike<str> {
+  #[inline]
+  fn as_path(&self) -> &Path {
+    Path::new(&self.0)
+  }
+}
+
+#[cfg(unix)]
+impl PathLike<[u8]> {
+  #[inline]
+  fn as_path(&self) -> &Path {
+    Path::new(OsStr::from_bytes(&self.0))
+  }
+}
+
+transparent_wrapper! {
+  /// A query wrapper to look up `OsStr` and `OsString` keys with `str` or, on
+  /// Unix, byte string queries, without converting them.
+  ///
+  /// The keys are compared to the query the way `OsStr` compares, i.e. byte by
+  /// byte, so the key `a/b` is distinct from `"a//b"` and less than `"a-b"`,
+  /// unlike with [`PathLike`]. The query hashes like an `OsStr`, so these keys
+  /// also implement [`EquivalentHash`].
+  #[derive(Debug, Default, Clone, Copy)]
+  pub struct OsStrLike<Q: ?Sized>(pub Q);
+}
+
+impl OsStrLike<str> {
+  #[inline]
+  fn as_os_str(&self) -> &OsStr {
+    OsStr::new(&self.0)
+  }
+}
+
+#[cfg(unix)]
+impl OsStrLike<[u8]> {
+  #[inline]
+  fn as_os_str(&self) -> &OsStr {
+    OsStr::from_bytes(&self.0)
+  }
+}
+
+macro_rules! impl_path_like {
+  (path: $($query:ty),+ $(,)?) => {
+    $(
+      impl Hash for PathLike<$query> {
+        #[inline]
+        fn hash<H: Hasher>(&self, state: &mut H) {
+          self.as_path().hash(state)
+        }
+      }
+
+      impl_path_like!(@key Path => PathLike<$query>, as_path: Path);
+      impl_path_like!(@key PathBuf => PathLike<$query>, as_path: Path);
+    )+
+  };
+  (os_str: $($query:ty),+ $(,)?) => {
+    $(
+      impl Hash for OsStrLike<$query> {
+        #[inline]
+        fn hash<H: Hasher>(&self, state: &mut H) {
+          self.as_os_str().hash(state)
+        }
+      }
+
+      impl_path_like!(@key OsStr => OsStrLike<$query>, as_os_str: OsStr);
+      impl_path_like!(@key OsString => OsStrLike<$query>, as_os_str: OsStr);
+    )+
+  };
+  (@key $key:ty => $wrapper:ty, $as:ident: $target:ty) => {
+    impl Equivalent<$wrapper> for $key {
+      #[inline]
+      fn equivalent(&self, key: &$wrapper) -> bool {
+        AsRef::<$target>::as_ref(self) == key.$as()
+      }
+    }
+
+    impl Comparable<$wrapper> for $key {
+      #[inline]
+      fn compare(&self, key: &$wrapper) -> Ordering {
+        AsRef::<$target>::as_ref(self).cmp(key.$as())
+      }
+    }
+
+    impl EquivalentHash<$wrapper> for $key {
+      #[inline]
+      fn hash_like<H: Hasher>(&self, state: &mut H) {
+        AsRef::<$target>::as_ref(self).hash(state)
+      }
+    }
+  };
+}
+
+impl_path_like!(path: str);
+
+#[cfg(unix)]
+impl_path_like!(path: [u8]);
+
+impl_path_like!(os_str: str);
+
+#[cfg(unix)]
+impl_path_like!(os_str: [u8]);
+
+#[cfg(test)]
+mod tests {
+  use core::cmp::Ordering;
+  use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+  };
+
+  use super::{OsStrLike, PathLike};
+  use crate::{
+    tests::{hash, hash_like},
+    Comparable, Equivalent,
+  };
+
+  #[test]
+  fn paths_compare_by_component() {
+    let key = PathBuf::from("a/b");
+    for query in ["a/b", "a//b", "a/./b", "a/b/"] {
+      let query = PathLike::from_ref(query);
+      assert!(key.equivalent(query), "{:?}", query);
+      assert_eq!(key.compare(query), Ordering::Equal);
+      assert_eq!(hash_like::<_, PathLike<str>>(&key), hash(query));
+      assert_eq!(hash_like::<_, PathLike<str>>(Path::new("a/b")), hash(query));
+    }
+
+    assert!(!key.equivalent(PathLike::from_ref("a/c")));
+    // `a` < `a-b` as components, although `'/'` > `'-'`.
+    assert_eq!(key.compare(PathLike::from_ref("a-b")), Ordering::Less);
+    assert_eq!(key.compare(PathLike::from_ref("a")), Ordering::Greater);
+  }
+
+  #[test]
+  fn os_strs_compare_by_byte() {
+    let key = OsString::from("a/b");
+    let query = OsStrLike::from_ref("a/b");
+    assert!(key.equivalent(query));
+    assert_eq!(key.compare(query), Ordering::Equal);
+    assert_eq!(hash_like::<_, OsStrLike<str>>(&key), hash(query));
+    assert_eq!(
+      hash_like::<_, OsStrLike<str>>(OsStr::new("a/b")),
+      hash(query)
+    );
+
+    for query in ["a//b", "a/./b", "a/b/"] {
+      assert!(!key.equivalent(OsStrLike::from_ref(query)), "{:?}", query);
+    }
+    // Byte order: `'/'` > `'-'`.
+    assert_eq!(key.compare(OsStrLike::from_ref("a-b")), Ordering::Greater);
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn byte_queries() {
+    let path = PathBuf::from("a/b");
+    let bytes = PathLike::from_ref(&b"a//b"[..]);
+    assert!(path.equivalent(bytes));
+    assert_eq!(hash_like::<_, PathLike<[u8]>>(&path), hash(bytes));
+    assert_eq!(hash(bytes), hash(PathLike::from_ref("a//b")));
+
+    let os = OsString::from("foo");
+    let bytes = OsStrLike::from_ref(&b"foo"[..]);
+    assert!(os.equivalent(bytes));
+    assert_eq!(hash_like::<_, OsStrLike<[u8]>>(&os), hash(bytes));
+    assert_eq!(hash(bytes), hash(OsStrLike::from_ref("foo")));
+    assert_eq!(os.compare(OsStrLike::from_ref(&b"fop"[..])), Ordering::Less);
+  }
+}
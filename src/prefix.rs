@@ -0,0 +1,187 @@
+use core::{
+  cmp::Ordering,
+  ops::{Bound, RangeBounds},
+};
+
+use super::{Comparable, Equivalent};
+
+/// A range covering exactly the string or byte string keys which start with a
+/// prefix.
+///
+/// `Prefix<'_, [u8]>` applies to keys implementing `AsRef<[u8]>`, and
+/// `Prefix<'_, str>` to keys implementing `AsRef<str>`. It implements
+/// `RangeBounds<PrefixBound<'_, T>>`, so prefix scans reuse the range machinery
+/// of this crate, e.g. [`ComparableRangeBounds`](crate::ComparableRangeBounds)
+/// and [`SliceExt::range_slice`](crate::SliceExt::range_slice).
+///
+/// The end of the range is not computed by incrementing the last byte of the
+/// prefix, so there is no carry to propagate through trailing `0xFF` bytes, and
+/// a prefix made only of `0xFF` bytes (or an empty prefix) still has a bounded,
+/// correct end.
+#[derive(Debug)]
+pub struct Prefix<'a, T: ?Sized> {
+  start: PrefixBound<'a, T>,
+  end: PrefixBound<'a, T>,
+}
+
+impl<'a, T: ?Sized> Prefix<'a, T> {
+  /// Creates a range covering the keys which start with `prefix`.
+  #[inline]
+  pub fn new(prefix: &'a T) -> Self {
+    Self {
+      start: PrefixBound { prefix, end: false },
+      end: PrefixBound { prefix, end: true },
+    }
+  }
+
+  /// Returns the prefix.
+  #[inline]
+  pub fn prefix(&self) -> &'a T {
+    self.start.prefix
+  }
+}
+
+impl<T: ?Sized> Clone for Prefix<'_, T> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ?Sized> Copy for Prefix<'_, T> {}
+
+impl<'a, T: ?Sized> RangeBounds<PrefixBound<'a, T>> for Prefix<'a, T> {
+  #[inline]
+  fn start_bound(&self) -> Bound<&PrefixBound<'a, T>> {
+    Bound::Included(&self.start)
+  }
+
+  #[inline]
+  fn end_bound(&self) -> Bound<&PrefixBound<'a, T>> {
+    Bound::Excluded(&self.end)
+  }
+}
+
+/// A bound of a [`Prefix`] range.
+///
+/// The start bound is the prefix itself. The end bound sits right after every
+/// key starting with the prefix: such keys compare less than it, while every
+/// other key compares to it as it does to the prefix.
+#[derive(Debug)]
+pub struct PrefixBound<'a, T: ?Sized> {
+  prefix: &'a T,
+  end: bool,
+}
+
+impl<T: ?Sized> Clone for PrefixBound<'_, T> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ?Sized> Copy for PrefixBound<'_, T> {}
+
+#[inline]
+fn compare(key: &[u8], prefix: &[u8], end: bool) -> Ordering {
+  if end && key.starts_with(prefix) {
+    Ordering::Less
+  } else {
+    key.cmp(prefix)
+  }
+}
+
+impl<K> Equivalent<PrefixBound<'_, [u8]>> for K
+where
+  K: ?Sized + AsRef<[u8]>,
+{
+  #[inline]
+  fn equivalent(&self, key: &PrefixBound<'_, [u8]>) -> bool {
+    compare(self.as_ref(), key.prefix, key.end) == Ordering::Equal
+  }
+}
+
+impl<K> Comparable<PrefixBound<'_, [u8]>> for K
+where
+  K: ?Sized + AsRef<[u8]>,
+{
+  #[inline]
+  fn compare(&self, key: &PrefixBound<'_, [u8]>) -> Ordering {
+    compare(self.as_ref(), key.prefix, key.end)
+  }
+}
+
+impl<K> Equivalent<PrefixBound<'_, str>> for K
+where
+  K: ?Sized + AsRef<str>,
+{
+  #[inline]
+  fn equivalent(&self, key: &PrefixBound<'_, str>) -> bool {
+    compare(self.as_ref().as_bytes(), key.prefix.as_bytes(), key.end) == Ordering::Equal
+  }
+}
+
+impl<K> Comparable<PrefixBound<'_, str>> for K
+where
+  K: ?Sized + AsRef<str>,
+{
+  #[inline]
+  fn compare(&self, key: &PrefixBound<'_, str>) -> Ordering {
+    compare(self.as_ref().as_bytes(), key.prefix.as_bytes(), key.end)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{string::String, vec::Vec};
+
+  use super::Prefix;
+  use crate::{ComparableRangeBounds, SliceExt};
+
+  /// Every byte string of length up to 3 over the bytes 0, 1, 0xFE and 0xFF,
+  /// sorted.
+  fn keys() -> Vec<Vec<u8>> {
+    let alphabet = [0u8, 1, 0xFE, 0xFF];
+    let mut keys = Vec::from([Vec::new()]);
+    for len in 1..=3 {
+      let mut next = Vec::new();
+      for key in keys.iter().filter(|k| k.len() == len - 1) {
+        for &b in &alphabet {
+          let mut key = key.clone();
+          key.push(b);
+          next.push(key);
+        }
+      }
+      keys.extend(next);
+    }
+    keys.sort();
+    keys
+  }
+
+  #[test]
+  fn bytes_match_starts_with() {
+    let keys = keys();
+    for prefix in &keys {
+      let range = Prefix::new(&prefix[..]);
+      let expected: Vec<_> = keys.iter().filter(|k| k.starts_with(prefix)).collect();
+      let contained: Vec<_> = keys.iter().filter(|k| range.compare_contains(*k)).collect();
+      assert_eq!(contained, expected, "{:?}", prefix);
+      let slice: Vec<_> = keys.range_slice(&range).iter().collect();
+      assert_eq!(slice, expected, "{:?}", prefix);
+    }
+  }
+
+  #[test]
+  fn strings() {
+    let keys: Vec<String> = ["", "a", "ab", "abc", "abd", "ac", "b", "\u{10FFFF}"]
+      .iter()
+      .map(|s| String::from(*s))
+      .collect();
+    assert_eq!(keys.range_slice(&Prefix::new("ab")), &keys[2..5]);
+    assert_eq!(keys.range_slice(&Prefix::new("a")), &keys[1..6]);
+    assert_eq!(keys.range_slice(&Prefix::new("")), &keys[..]);
+    assert_eq!(keys.range_slice(&Prefix::new("\u{10FFFF}")), &keys[7..]);
+    assert!(keys.range_slice(&Prefix::new("abe")).is_empty());
+    assert_eq!(Prefix::new("ab").prefix(), "ab");
+  }
+}
@@ -0,0 +1,140 @@
+use core::{cmp::Ordering, fmt};
+
+use super::{Comparable, Equivalent};
+
+/// A query which looks up stored values by one of their fields, or any other
+/// key extracted from them by a function.
+///
+/// A stored `T` is compared to `Project<F, Q>` by comparing `projection(&t)`
+/// to the query, so a set of `User { id, name, .. }` records sorted by `id`
+/// can be searched with `Project::new(&id, |u: &User| &u.id)`, behaving like a
+/// map keyed on `id`.
+///
+/// The container must be ordered (or, for [`Equivalent`], partitioned)
+/// consistently with the projected key. `Project` does not implement
+/// [`EquivalentHash`](crate::EquivalentHash), as hashing a stored value needs
+/// the projection, which only the query holds.
+pub struct Project<'a, F, Q: ?Sized> {
+  query: &'a Q,
+  projection: F,
+}
+
+impl<'a, F, Q: ?Sized> Project<'a, F, Q> {
+  /// Creates a query comparing `projection(stored)` against `query`.
+  ///
+  /// The bound on `projection` lets closures such as `|u: &User| &u.id` be
+  /// inferred to return a reference borrowed from their argument.
+  #[inline]
+  pub fn new<T, P>(query: &'a Q, projection: F) -> Self
+  where
+    T: ?Sized,
+    P: ?Sized,
+    F: Fn(&T) -> &P,
+  {
+    Self { query, projection }
+  }
+
+  /// Returns the query which projected keys are compared against.
+  #[inline]
+  pub fn query(&self) -> &'a Q {
+    self.query
+  }
+}
+
+impl<F: Clone, Q: ?Sized> Clone for Project<'_, F, Q> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      query: self.query,
+      projection: self.projection.clone(),
+    }
+  }
+}
+
+impl<F: Copy, Q: ?Sized> Copy for Project<'_, F, Q> {}
+
+impl<F, Q: ?Sized + fmt::Debug> fmt::Debug for Project<'_, F, Q> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("Project").field(&self.query).finish()
+  }
+}
+
+impl<T, P, Q, F> Equivalent<Project<'_, F, Q>> for T
+where
+  T: ?Sized,
+  P: ?Sized + Equivalent<Q>,
+  Q: ?Sized,
+  F: Fn(&T) -> &P,
+{
+  #[inline]
+  fn equivalent(&self, key: &Project<'_, F, Q>) -> bool {
+    (key.projection)(self).equivalent(key.query)
+  }
+}
+
+impl<T, P, Q, F> Comparable<Project<'_, F, Q>> for T
+where
+  T: ?Sized,
+  P: ?Sized + Comparable<Q>,
+  Q: ?Sized,
+  F: Fn(&T) -> &P,
+{
+  #[inline]
+  fn compare(&self, key: &Project<'_, F, Q>) -> Ordering {
+    (key.projection)(self).compare(key.query)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::cmp::Ordering;
+  use std::{string::String, vec::Vec};
+
+  use super::Project;
+  use crate::{Comparable, Equivalent, SliceExt};
+
+  struct User {
+    id: u32,
+    name: String,
+  }
+
+  fn users() -> Vec<User> {
+    [(1, "ann"), (3, "bob"), (4, "cid"), (8, "dee")]
+      .iter()
+      .map(|&(id, name)| User {
+        id,
+        name: String::from(name),
+      })
+      .collect()
+  }
+
+  #[test]
+  fn compare_projected_key() {
+    let user = &users()[1];
+    assert!(user.equivalent(&Project::new(&3, |u: &User| &u.id)));
+    assert!(!user.equivalent(&Project::new(&4, |u: &User| &u.id)));
+    assert_eq!(
+      user.compare(&Project::new(&4, |u: &User| &u.id)),
+      Ordering::Less
+    );
+    assert_eq!(
+      user.compare(&Project::new("bob", |u: &User| &u.name)),
+      Ordering::Equal
+    );
+    assert_eq!(Project::new("bob", |u: &User| &u.name).query(), "bob");
+  }
+
+  #[test]
+  fn search_by_field() {
+    let users = users();
+    let by_id = |id: &u32| users.binary_search_comparable(&Project::new(id, |u: &User| &u.id));
+    assert_eq!(by_id(&4), Ok(2));
+    assert_eq!(by_id(&5), Err(3));
+    assert_eq!(by_id(&0), Err(0));
+
+    // Sorted by `id`, the users happen to be sorted by `name` too.
+    let by_name = Project::new("cid", |u: &User| u.name.as_str());
+    assert_eq!(users.lower_bound(&by_name), 2);
+    assert_eq!(users.upper_bound(&by_name), 3);
+  }
+}
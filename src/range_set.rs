@@ -0,0 +1,507 @@
+//! A set of items given as a union of ranges, which can be tested for
+//! containment with any `Q` where `T: Comparable<Q>`.
+//!
+//! The ranges are kept normalized: sorted, not empty, and neither overlapping
+//! nor touching, so under dense-order semantics, two sets with the same items
+//! hold the same ranges. As with [`ComparableRangeBounds`], ranges are treated
+//! as intervals of a dense order, e.g. `0..1` and `1..2` touch and are merged
+//! into `0..2`, but `0..=1` and `2..3` are not, even though they hold the same
+//! integers as `0..3`.
+
+use alloc::{vec, vec::Vec};
+use core::{
+  cmp::Ordering,
+  fmt,
+  iter::FromIterator,
+  ops::{Bound, RangeBounds},
+  slice,
+};
+
+use super::{
+  cmp_lower, cmp_upper, lower_reaches_upper, lower_touches_upper, Comparable,
+  ComparableRangeBounds, Interval,
+};
+
+/// A set of `T` items given as a union of normalized [`Interval`]s.
+///
+/// [`contains`](RangeSet::contains) accepts any `Q` where `T: Comparable<Q>`.
+/// The `Comparable<Q>` implementation must be consistent with the `Ord`
+/// implementation of `T`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+  ranges: Vec<Interval<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RangeSet<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_set().entries(self.ranges.iter()).finish()
+  }
+}
+
+impl<T> RangeSet<T> {
+  /// Creates an empty set.
+  #[inline]
+  pub fn new() -> Self {
+    Self { ranges: Vec::new() }
+  }
+
+  /// Creates a set containing every item.
+  #[inline]
+  pub fn full() -> Self {
+    Self {
+      ranges: vec![Interval::full()],
+    }
+  }
+
+  /// Returns `true` if the set contains no items.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.ranges.is_empty()
+  }
+
+  /// Returns the normalized ranges of the set, in order.
+  #[inline]
+  pub fn ranges(&self) -> &[Interval<T>] {
+    &self.ranges
+  }
+
+  /// Returns an iterator over the normalized ranges of the set, in order.
+  #[inline]
+  pub fn iter(&self) -> slice::Iter<'_, Interval<T>> {
+    self.ranges.iter()
+  }
+
+  /// Removes all the items of the set.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.ranges.clear();
+  }
+
+  /// Returns `true` if `item` is contained in one of the ranges of the set.
+  #[inline]
+  pub fn contains<Q>(&self, item: &Q) -> bool
+  where
+    T: Comparable<Q>,
+    Q: ?Sized,
+  {
+    let item = Bound::Included(item);
+    let index = self.ranges.partition_point(|r| {
+      !lower_reaches_upper(item, r.end_bound(), |q: &Q, t: &T| t.compare(q).reverse())
+    });
+    self.ranges.get(index).map_or(false, |r| {
+      lower_reaches_upper(r.start_bound(), item, |t: &T, q: &Q| t.compare(q))
+    })
+  }
+
+  /// Adds the items of `range` to the set.
+  ///
+  /// Unlike [`contains`](RangeSet::contains), which only compares its query
+  /// with the bounds of the set, this stores the bounds of `range`, so it takes
+  /// any range of owned `T`s which converts into an [`Interval`].
+  pub fn insert<R>(&mut self, range: R)
+  where
+    T: Ord,
+    R: Into<Interval<T>>,
+  {
+    let range = range.into();
+    if is_empty(&range) {
+      return;
+    }
+
+    // The ranges in `lo..hi` overlap or touch `range`, and are merged with it.
+    let lo = self
+      .ranges
+      .partition_point(|r| !lower_touches_upper(range.start_bound(), r.end_bound(), Ord::cmp));
+    let hi = lo
+      + self.ranges[lo..]
+        .partition_point(|r| lower_touches_upper(r.start_bound(), range.end_bound(), Ord::cmp));
+    if lo == hi {
+      self.ranges.insert(lo, range);
+      return;
+    }
+
+    let mut merged = self.ranges.drain(lo..hi);
+    let first = merged.next().unwrap();
+    let last = merged.next_back();
+    drop(merged);
+    let (first_start, last_end) = match last {
+      Some(last) => (first.start, last.end),
+      None => (first.start, first.end),
+    };
+
+    let Interval { start, end } = range;
+    let start = match cmp_lower(first_start.as_ref(), start.as_ref(), Ord::cmp) {
+      Ordering::Less => first_start,
+      _ => start,
+    };
+    let end = match cmp_upper(last_end.as_ref(), end.as_ref(), Ord::cmp) {
+      Ordering::Greater => last_end,
+      _ => end,
+    };
+    self.ranges.insert(lo, Interval::new(start, end));
+  }
+
+  /// Removes the items of `range` from the set.
+  ///
+  /// Like [`insert`](RangeSet::insert), this takes a range of owned `T`s, as
+  /// the bounds of `range` become the bounds of the ranges it cuts.
+  pub fn remove<R>(&mut self, range: R)
+  where
+    T: Ord,
+    R: Into<Interval<T>>,
+  {
+    let range = range.into();
+    if is_empty(&range) {
+      return;
+    }
+
+    // The ranges in `lo..hi` overlap `range`, and are cut by it.
+    let lo = self
+      .ranges
+      .partition_point(|r| !lower_reaches_upper(range.start_bound(), r.end_bound(), Ord::cmp));
+    let hi = lo
+      + self.ranges[lo..]
+        .partition_point(|r| lower_reaches_upper(r.start_bound(), range.end_bound(), Ord::cmp));
+    if lo == hi {
+      return;
+    }
+
+    let mut cut = self.ranges.drain(lo..hi);
+    let first = cut.next().unwrap();
+    let last = cut.next_back();
+    drop(cut);
+    let (first_start, last_end) = match last {
+      Some(last) => (first.start, last.end),
+      None => (first.start, first.end),
+    };
+
+    // The pieces of the cut ranges below and above `range`, if any.
+    let right = match range.end {
+      Bound::Unbounded => None,
+      end => Some(Interval::new(flip(end), last_end)),
+    };
+    if let Some(right) = right.filter(|r| !is_empty(r)) {
+      self.ranges.insert(lo, right);
+    }
+    let left = match range.start {
+      Bound::Unbounded => None,
+      start => Some(Interval::new(first_start, flip(start))),
+    };
+    if let Some(left) = left.filter(|r| !is_empty(r)) {
+      self.ranges.insert(lo, left);
+    }
+  }
+
+  /// Returns the set of the items contained in `self` or `other`.
+  pub fn union(&self, other: &Self) -> Self
+  where
+    T: Ord + Clone,
+  {
+    let mut ranges: Vec<Interval<T>> = Vec::with_capacity(self.ranges.len() + other.ranges.len());
+    let (mut a, mut b) = (
+      self.ranges.iter().peekable(),
+      other.ranges.iter().peekable(),
+    );
+    loop {
+      let next = match (a.peek(), b.peek()) {
+        (Some(x), Some(y)) => {
+          if cmp_lower(x.start_bound(), y.start_bound(), Ord::cmp) == Ordering::Greater {
+            b.next()
+          } else {
+            a.next()
+          }
+        }
+        (Some(_), None) => a.next(),
+        (None, _) => b.next(),
+      };
+      let next = match next {
+        Some(next) => next,
+        None => break,
+      };
+
+      // `next` starts after the start of the last range, so they are merged
+      // if it starts before the end of the last range.
+      match ranges.last_mut() {
+        Some(last) if lower_touches_upper(next.start_bound(), last.end_bound(), Ord::cmp) => {
+          if cmp_upper(next.end_bound(), last.end_bound(), Ord::cmp) == Ordering::Greater {
+            last.end = next.end_bound().cloned();
+          }
+        }
+        _ => ranges.push(next.clone()),
+      }
+    }
+    Self { ranges }
+  }
+
+  /// Returns the set of the items contained in both `self` and `other`.
+  pub fn intersection(&self, other: &Self) -> Self
+  where
+    T: Ord + Clone,
+  {
+    let mut ranges = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < self.ranges.len() && j < other.ranges.len() {
+      let (a, b) = (&self.ranges[i], &other.ranges[j]);
+      if let Some((start, end)) = ComparableRangeBounds::<T>::compare_intersection(a, b) {
+        ranges.push(Interval::new(start.cloned(), end.cloned()));
+      }
+      // The range which ends first cannot intersect the following ranges of
+      // the other set.
+      if cmp_upper(a.end_bound(), b.end_bound(), Ord::cmp) == Ordering::Greater {
+        j += 1;
+      } else {
+        i += 1;
+      }
+    }
+    Self { ranges }
+  }
+
+  /// Returns the set of the items contained in `self` but not in `other`.
+  pub fn difference(&self, other: &Self) -> Self
+  where
+    T: Ord + Clone,
+  {
+    let mut ranges = Vec::new();
+    let mut j = 0;
+    for a in &self.ranges {
+      // The ranges of `other` before `j` end before `a`, and so before the
+      // following ranges of `self` too.
+      while j < other.ranges.len()
+        && !lower_reaches_upper(a.start_bound(), other.ranges[j].end_bound(), Ord::cmp)
+      {
+        j += 1;
+      }
+
+      // Keep the pieces of `a` between the ranges of `other` which overlap it,
+      // from `start` on, until one of them is unbounded above.
+      let mut start = Some(a.start.clone());
+      for b in &other.ranges[j..] {
+        if !lower_reaches_upper(b.start_bound(), a.end_bound(), Ord::cmp) {
+          break;
+        }
+        if b.start != Bound::Unbounded {
+          if let Some(start) = start.take() {
+            let piece = Interval::new(start, flip(b.start.clone()));
+            if !is_empty(&piece) {
+              ranges.push(piece);
+            }
+          }
+        }
+        start = match b.end {
+          Bound::Unbounded => None,
+          ref end => Some(flip(end.clone())),
+        };
+      }
+      if let Some(start) = start {
+        let piece = Interval::new(start, a.end.clone());
+        if !is_empty(&piece) {
+          ranges.push(piece);
+        }
+      }
+    }
+    Self { ranges }
+  }
+
+  /// Returns the set of the items not contained in `self`.
+  pub fn complement(&self) -> Self
+  where
+    T: Clone,
+  {
+    let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+    let mut start = Bound::Unbounded;
+    for r in &self.ranges {
+      match r.start_bound() {
+        Bound::Unbounded => {}
+        bound => ranges.push(Interval::new(start, flip(bound.cloned()))),
+      }
+      start = match r.end_bound() {
+        // The ranges do not touch, so only the last one can be unbounded.
+        Bound::Unbounded => return Self { ranges },
+        bound => flip(bound.cloned()),
+      };
+    }
+    ranges.push(Interval::new(start, Bound::Unbounded));
+    Self { ranges }
+  }
+}
+
+impl<T: Ord, R: Into<Interval<T>>> FromIterator<R> for RangeSet<T> {
+  fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+    let mut set = Self::new();
+    set.extend(iter);
+    set
+  }
+}
+
+impl<T: Ord, R: Into<Interval<T>>> Extend<R> for RangeSet<T> {
+  fn extend<I: IntoIterator<Item = R>>(&mut self, iter: I) {
+    for r in iter {
+      self.insert(r);
+    }
+  }
+}
+
+impl<'a, T> IntoIterator for &'a RangeSet<T> {
+  type Item = &'a Interval<T>;
+  type IntoIter = slice::Iter<'a, Interval<T>>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<T> IntoIterator for RangeSet<T> {
+  type Item = Interval<T>;
+  type IntoIter = vec::IntoIter<Interval<T>>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.ranges.into_iter()
+  }
+}
+
+#[inline]
+fn is_empty<T: Ord>(range: &Interval<T>) -> bool {
+  ComparableRangeBounds::<T>::compare_is_empty(range)
+}
+
+/// Turns a lower bound into the upper bound of the items below it, and an
+/// upper bound into the lower bound of the items above it.
+#[inline]
+fn flip<T>(bound: Bound<T>) -> Bound<T> {
+  match bound {
+    Bound::Included(t) => Bound::Excluded(t),
+    Bound::Excluded(t) => Bound::Included(t),
+    Bound::Unbounded => Bound::Unbounded,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::ops::{Bound, RangeBounds};
+  use std::vec::Vec;
+
+  use super::RangeSet;
+  use crate::{
+    lower_touches_upper,
+    tests::{contains, points, ranges, xorshift, Range},
+    ComparableRangeBounds,
+  };
+
+  /// The sample points contained in a set, see [`points`].
+  fn bitmap(set: &RangeSet<i32>) -> Vec<bool> {
+    points().into_iter().map(|p| set.contains(&p)).collect()
+  }
+
+  fn range_bitmap(range: &Range) -> Vec<bool> {
+    points().into_iter().map(|p| contains(range, p)).collect()
+  }
+
+  fn zip(a: &[bool], b: &[bool], f: impl Fn(bool, bool) -> bool) -> Vec<bool> {
+    a.iter().zip(b).map(|(&a, &b)| f(a, b)).collect()
+  }
+
+  /// Checks that the ranges of `set` are normalized, and that it holds the
+  /// items of `expected`.
+  fn check(set: &RangeSet<i32>, expected: &[bool]) {
+    for r in set {
+      assert!(
+        !ComparableRangeBounds::<i32>::compare_is_empty(r),
+        "{:?}",
+        set
+      );
+    }
+    for pair in set.ranges().windows(2) {
+      assert!(
+        !lower_touches_upper(pair[1].start_bound(), pair[0].end_bound(), Ord::cmp),
+        "{:?}",
+        set
+      );
+    }
+    assert_eq!(bitmap(set), expected, "{:?}", set);
+    assert_eq!(set.is_empty(), !expected.contains(&true));
+  }
+
+  /// A deterministic sequence of sets, built by inserting and removing
+  /// ranges, along with their bitmaps.
+  fn sets() -> Vec<(RangeSet<i32>, Vec<bool>)> {
+    let ranges = ranges();
+    let mut random = xorshift();
+    let mut next = move || random.next().unwrap() as usize;
+
+    let mut sets = Vec::new();
+    let mut set = RangeSet::new();
+    let mut model = bitmap(&set);
+    for i in 0..200 {
+      let range = ranges[next() % ranges.len()];
+      let items = range_bitmap(&range);
+      if next() % 3 == 0 {
+        set.remove(range);
+        model = zip(&model, &items, |a, b| a && !b);
+      } else {
+        set.insert(range);
+        model = zip(&model, &items, |a, b| a || b);
+      }
+      check(&set, &model);
+      if i % 4 == 0 {
+        sets.push((set.clone(), model.clone()));
+      }
+      if i % 20 == 19 {
+        set.clear();
+        model = bitmap(&set);
+      }
+    }
+    sets
+  }
+
+  #[test]
+  fn insert_and_remove_match_bitmap() {
+    let sets = sets();
+    assert!(sets.iter().any(|(set, _)| set.ranges().len() > 1));
+    // Sets with the same items hold the same ranges.
+    for (a, model_a) in &sets {
+      for (b, model_b) in &sets {
+        assert_eq!(a == b, model_a == model_b, "{:?} {:?}", a, b);
+      }
+    }
+  }
+
+  #[test]
+  fn set_operations_match_bitmap() {
+    let sets = sets();
+    for (a, model_a) in &sets {
+      check(&a.complement(), &zip(model_a, model_a, |a, _| !a));
+      for (b, model_b) in &sets {
+        check(&a.union(b), &zip(model_a, model_b, |a, b| a || b));
+        check(&a.intersection(b), &zip(model_a, model_b, |a, b| a && b));
+        check(&a.difference(b), &zip(model_a, model_b, |a, b| a && !b));
+      }
+    }
+  }
+
+  #[test]
+  fn full_and_empty() {
+    let full = RangeSet::full();
+    check(&full, &[true; 11]);
+    check(&full.complement(), &[false; 11]);
+    check(&RangeSet::new().complement(), &[true; 11]);
+
+    let mut set: RangeSet<i32> = RangeSet::new();
+    set.insert(5..5);
+    set.insert((Bound::Excluded(3), Bound::Included(3)));
+    assert!(set.is_empty());
+    set.insert(0..1);
+    set.insert(1..2);
+    assert_eq!(set.ranges().len(), 1);
+    set.remove(3..3);
+    assert_eq!(set.ranges().len(), 1);
+  }
+}
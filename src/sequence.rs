@@ -0,0 +1,232 @@
+use core::{
+  cmp::Ordering,
+  hash::{Hash, Hasher},
+};
+
+use super::{Comparable, Equivalent, EquivalentHash};
+
+transparent_wrapper! {
+  /// A query wrapper for sequence keys, comparing them element by element.
+  ///
+  /// A `Vec<K>` or `[K; N]` key only `Borrow`s a `[K]`, so the blanket
+  /// implementations cannot look it up with a `[Q]` when `K != Q`. Wrapping the
+  /// query slice in `Sequence` makes `[K]`, `[K; N]`, `Vec<K>` and `Box<[K]>`
+  /// keys implement:
+  ///
+  /// - [`Equivalent`], if both sequences have the same length and every `K` is
+  ///   equivalent to the `Q` at the same position.
+  /// - [`Comparable`], in lexicographic order, like slices do.
+  /// - [`EquivalentHash`], where `Sequence<[Q]>` hashes exactly like `[Q]` and
+  ///   the keys exactly like `[K]`.
+  ///
+  /// Slices hash their elements through `Hash::hash_slice`, which may write
+  /// them all at once, so the keys are hashed through the `Hash`
+  /// implementation of `K` rather than through `EquivalentHash<Q>`. `K` must
+  /// therefore already hash like `Q` through `Hash`, as the [`Equivalent`]
+  /// contract requires, e.g. `String` and `str`: keys whose `Hash` differs
+  /// from that of the queries cannot be found with a `Sequence` in a hash map.
+  #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+  pub struct Sequence<Q: ?Sized>(pub Q);
+}
+
+impl<Q: Hash> Hash for Sequence<[Q]> {
+  #[inline]
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.0.hash(state)
+  }
+}
+
+#[inline]
+fn equivalent<K: Equivalent<Q>, Q>(keys: &[K], queries: &[Q]) -> bool {
+  keys.len() == queries.len() && keys.iter().zip(queries).all(|(k, q)| k.equivalent(q))
+}
+
+#[inline]
+fn compare<K: Comparable<Q>, Q>(keys: &[K], queries: &[Q]) -> Ordering {
+  for (k, q) in keys.iter().zip(queries) {
+    match k.compare(q) {
+      Ordering::Equal => {}
+      ord => return ord,
+    }
+  }
+  keys.len().cmp(&queries.len())
+}
+
+/// `[Q]` hashes its elements through `Q::hash_slice`, which writes primitives
+/// in a single call, so the keys are hashed through `K::hash_slice` rather
+/// than one `hash_like` at a time, see [`Sequence`].
+#[inline]
+fn hash_like<K: Hash, H: Hasher>(keys: &[K], state: &mut H) {
+  keys.hash(state)
+}
+
+macro_rules! impl_sequence {
+  ($([$($generics:tt)*] $ty:ty),+ $(,)?) => {
+    $(
+      impl<K, Q, $($generics)*> Equivalent<Sequence<[Q]>> for $ty
+      where
+        K: Equivalent<Q>,
+      {
+        #[inline]
+        fn equivalent(&self, key: &Sequence<[Q]>) -> bool {
+          equivalent(self, &key.0)
+        }
+      }
+
+      impl<K, Q, $($generics)*> Comparable<Sequence<[Q]>> for $ty
+      where
+        K: Comparable<Q>,
+      {
+        #[inline]
+        fn compare(&self, key: &Sequence<[Q]>) -> Ordering {
+          compare(self, &key.0)
+        }
+      }
+
+      impl<K, Q, $($generics)*> EquivalentHash<Sequence<[Q]>> for $ty
+      where
+        K: Equivalent<Q> + Hash,
+        Q: Hash,
+      {
+        #[inline]
+        fn hash_like<H: Hasher>(&self, state: &mut H) {
+          hash_like(self, state)
+        }
+      }
+    )+
+  };
+}
+
+impl_sequence!([] [K], [const N: usize] [K; N]);
+
+#[cfg(feature = "alloc")]
+impl_sequence!([] alloc::vec::Vec<K>, [] alloc::boxed::Box<[K]>);
+
+#[cfg(test)]
+mod tests {
+  use core::hash::{Hash, Hasher};
+  use std::vec::Vec;
+
+  use super::Sequence;
+  use crate::{Comparable, Equivalent, EquivalentHash};
+
+  /// Records every write made to it, to tell apart a hash which writes a
+  /// slice at once from one which writes it element by element.
+  #[derive(Default)]
+  struct Recorder(Vec<Vec<u8>>);
+
+  impl Hasher for Recorder {
+    fn finish(&self) -> u64 {
+      0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+      self.0.push(bytes.to_vec());
+    }
+  }
+
+  fn record<T: ?Sized + Hash>(value: &T) -> Vec<Vec<u8>> {
+    let mut state = Recorder::default();
+    value.hash(&mut state);
+    state.0
+  }
+
+  fn record_like<K: ?Sized + EquivalentHash<Sequence<[u8]>>>(key: &K) -> Vec<Vec<u8>> {
+    let mut state = Recorder::default();
+    key.hash_like(&mut state);
+    state.0
+  }
+
+  #[test]
+  fn ordering_matches_slices() {
+    let samples: &[&[u32]] = &[&[], &[0], &[0, 0], &[0, 1], &[1], &[1, 0, 0]];
+    for &a in samples {
+      for &b in samples {
+        assert_eq!(a.compare(Sequence::from_ref(b)), a.cmp(b));
+        assert_eq!(a.equivalent(Sequence::from_ref(b)), a == b);
+      }
+    }
+  }
+
+  #[test]
+  fn hash_matches_slices_write_by_write() {
+    let bytes: &[u8] = &[1, 2, 3];
+    let query = Sequence::from_ref(bytes);
+    // `[u8]` writes its elements at once, so a per-element hash would differ
+    // under hashers which do not just concatenate their input.
+    assert_eq!(record(query), record(bytes));
+    assert_eq!(record(&[1u8, 2, 3]), record(query));
+    assert_eq!(record_like(bytes), record(query));
+    assert_eq!(record_like(&[1u8, 2, 3]), record(query));
+  }
+
+  #[cfg(feature = "alloc")]
+  mod alloc {
+    use core::cmp::Ordering;
+    use std::{boxed::Box, string::String, vec};
+
+    use super::{record, record_like, Sequence};
+    use crate::{
+      tests::{hash, hash_like},
+      Comparable, Composite, Equivalent,
+    };
+
+    type Pair = (String, u32);
+    type PairQuery<'a> = Composite<(&'a str, &'a u32)>;
+
+    fn pair(s: &str, n: u32) -> Pair {
+      (String::from(s), n)
+    }
+
+    #[test]
+    fn equivalent_and_compare() {
+      let keys = vec![pair("a", 1), pair("b", 2)];
+      let query: &[PairQuery] = &[Composite(("a", &1)), Composite(("b", &2))];
+      assert!(keys.equivalent(Sequence::from_ref(query)));
+      assert_eq!(keys.compare(Sequence::from_ref(query)), Ordering::Equal);
+
+      let shorter = &query[..1];
+      assert!(!keys.equivalent(Sequence::from_ref(shorter)));
+      assert_eq!(keys.compare(Sequence::from_ref(shorter)), Ordering::Greater);
+
+      let greater: &[PairQuery] = &[Composite(("a", &1)), Composite(("c", &0))];
+      assert!(!keys.equivalent(Sequence::from_ref(greater)));
+      assert_eq!(keys.compare(Sequence::from_ref(greater)), Ordering::Less);
+    }
+
+    #[test]
+    fn all_key_types() {
+      let query: &[PairQuery] = &[Composite(("x", &1)), Composite(("y", &2))];
+      let query = Sequence::from_ref(query);
+      let array = [pair("x", 1), pair("y", 2)];
+      let vec = array.to_vec();
+      let boxed: Box<[Pair]> = vec.clone().into_boxed_slice();
+
+      assert!(array.equivalent(query));
+      assert!(array[..].equivalent(query));
+      assert!(vec.equivalent(query));
+      assert!(boxed.equivalent(query));
+      assert_eq!(array.compare(query), Ordering::Equal);
+      assert_eq!(array[..].compare(query), Ordering::Equal);
+      assert_eq!(vec.compare(query), Ordering::Equal);
+      assert_eq!(boxed.compare(query), Ordering::Equal);
+
+      assert_eq!(hash_like::<_, Sequence<[PairQuery]>>(&array), hash(query));
+      assert_eq!(
+        hash_like::<[Pair], Sequence<[PairQuery]>>(&array[..]),
+        hash(query)
+      );
+      assert_eq!(hash_like::<_, Sequence<[PairQuery]>>(&vec), hash(query));
+      assert_eq!(hash_like::<_, Sequence<[PairQuery]>>(&boxed), hash(query));
+    }
+
+    #[test]
+    fn owned_bytes_hash_like_slices() {
+      let bytes: &[u8] = &[1, 2, 3];
+      let query = Sequence::from_ref(bytes);
+      assert_eq!(record(&bytes.to_vec()), record(query));
+      assert_eq!(record_like(&bytes.to_vec()), record(query));
+      assert_eq!(record_like(&Box::<[u8]>::from(bytes)), record(query));
+    }
+  }
+}
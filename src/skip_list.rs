@@ -0,0 +1,537 @@
+//! An arena-allocated skip list, which can be queried with any `Q` where
+//! `K: Comparable<Q>`.
+//!
+//! The nodes and their links are stored in two vectors, and linked by index,
+//! so inserting allocates nothing but the occasional growth of the vectors.
+//! As in the memtable of an LSM tree, which is dropped as a whole once
+//! flushed, entries cannot be removed one by one: inserting an existing key
+//! replaces its value, and [`clear`](SkipList::clear) empties the list while
+//! keeping its allocations.
+//!
+//! The list is single-threaded; it is not a concurrent skip list.
+
+use alloc::vec::Vec;
+use core::{
+  cmp::Ordering,
+  fmt,
+  iter::{FromIterator, FusedIterator},
+  mem,
+  ops::Bound,
+};
+
+use super::{Comparable, ComparableRangeBounds};
+
+/// The maximum height of a tower, which keeps searches logarithmic for
+/// millions of entries, with a branching factor of 4.
+const MAX_HEIGHT: usize = 12;
+
+/// The null link, which also stands for the head of the list where a node is
+/// expected.
+const NIL: usize = !0;
+
+#[derive(Clone)]
+struct Node<K, V> {
+  key: K,
+  value: V,
+  /// The offset of the tower of links of this node in the link arena.
+  tower: usize,
+}
+
+/// An ordered map backed by a skip list whose nodes are allocated in an
+/// arena.
+///
+/// Every lookup accepts any `Q` where `K: Comparable<Q>`, and
+/// [`range`](SkipList::range) any `R: ComparableRangeBounds<Q>`. The
+/// `Comparable<Q>` implementation must be consistent with the `Ord`
+/// implementation of `K`.
+#[derive(Clone)]
+pub struct SkipList<K, V> {
+  nodes: Vec<Node<K, V>>,
+  links: Vec<usize>,
+  head: [usize; MAX_HEIGHT],
+  height: usize,
+  seed: u32,
+}
+
+impl<K, V> Default for SkipList<K, V> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for SkipList<K, V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<K, V> SkipList<K, V> {
+  /// Creates an empty list.
+  #[inline]
+  pub fn new() -> Self {
+    Self::with_capacity(0)
+  }
+
+  /// Creates an empty list with space for at least `capacity` entries.
+  #[inline]
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      nodes: Vec::with_capacity(capacity),
+      // A tower has 4/3 links on average.
+      links: Vec::with_capacity(capacity + capacity / 3),
+      head: [NIL; MAX_HEIGHT],
+      height: 1,
+      seed: 0x9e37_79b9,
+    }
+  }
+
+  /// Returns the number of entries in the list.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.nodes.len()
+  }
+
+  /// Returns `true` if the list contains no entries.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.nodes.is_empty()
+  }
+
+  /// Removes all the entries of the list, keeping its allocations.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.nodes.clear();
+    self.links.clear();
+    self.head = [NIL; MAX_HEIGHT];
+    self.height = 1;
+  }
+
+  /// Returns an iterator over the entries of the list, in key order.
+  #[inline]
+  pub fn iter(&self) -> Iter<'_, K, V> {
+    Iter {
+      list: self,
+      node: self.head[0],
+      end: NIL,
+    }
+  }
+
+  /// Returns the entry with the least key.
+  #[inline]
+  pub fn first(&self) -> Option<(&K, &V)> {
+    self.entry(self.head[0])
+  }
+
+  /// Returns the entry with the greatest key.
+  #[inline]
+  pub fn last(&self) -> Option<(&K, &V)> {
+    self.entry(self.seek(|_| true, None))
+  }
+
+  /// Returns the entry with a key equivalent to `key`.
+  #[inline]
+  pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.entry(self.find(key))
+  }
+
+  /// Returns a reference to the value of the key equivalent to `key`.
+  #[inline]
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.get_key_value(key).map(|(_, v)| v)
+  }
+
+  /// Returns a mutable reference to the value of the key equivalent to `key`.
+  #[inline]
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    match self.find(key) {
+      NIL => None,
+      node => Some(&mut self.nodes[node].value),
+    }
+  }
+
+  /// Returns `true` if the list contains a key equivalent to `key`.
+  #[inline]
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.find(key) != NIL
+  }
+
+  /// Returns the entry with the least key above `bound`, i.e. the first
+  /// entry whose key is greater than or equal to an `Included` bound, or
+  /// greater than an `Excluded` one.
+  #[inline]
+  pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Option<(&K, &V)>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    let pred = self.seek_below(bound);
+    self.entry(self.link(pred, 0))
+  }
+
+  /// Returns the entry with the greatest key below `bound`, i.e. the last
+  /// entry whose key is less than or equal to an `Included` bound, or less
+  /// than an `Excluded` one.
+  #[inline]
+  pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Option<(&K, &V)>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.entry(self.seek_through(bound))
+  }
+
+  /// Returns an iterator over the entries whose keys are contained in `range`,
+  /// in key order.
+  pub fn range<Q, R>(&self, range: &R) -> Iter<'_, K, V>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + ComparableRangeBounds<Q>,
+  {
+    let node = self.link(self.seek_below(range.start_bound()), 0);
+    // The first node above the start bound is either contained in the range,
+    // or beyond its end, e.g. if the range is empty.
+    if node == NIL || !range.compare_contains(&self.nodes[node].key) {
+      return Iter {
+        list: self,
+        node: NIL,
+        end: NIL,
+      };
+    }
+
+    let end = self.link(self.seek_through(range.end_bound()), 0);
+    Iter {
+      list: self,
+      node,
+      end,
+    }
+  }
+
+  /// Inserts a key-value pair into the list, returning the previous value of
+  /// the key, if any.
+  ///
+  /// As with the standard maps, the key itself is not updated if it was
+  /// already present.
+  pub fn insert(&mut self, key: K, value: V) -> Option<V>
+  where
+    K: Ord,
+  {
+    let mut preds = [NIL; MAX_HEIGHT];
+    let pred = self.seek(|k| *k < key, Some(&mut preds));
+    let next = self.link(pred, 0);
+    if next != NIL && self.nodes[next].key == key {
+      return Some(mem::replace(&mut self.nodes[next].value, value));
+    }
+
+    let height = self.random_height();
+    if height > self.height {
+      // `preds` is already `NIL`, i.e. the head, above the current height.
+      self.height = height;
+    }
+
+    let node = self.nodes.len();
+    let tower = self.links.len();
+    for (level, &pred) in preds.iter().enumerate().take(height) {
+      let next = self.link(pred, level);
+      self.links.push(next);
+      self.set_link(pred, level, node);
+    }
+    self.nodes.push(Node { key, value, tower });
+    None
+  }
+
+  #[inline]
+  fn entry(&self, node: usize) -> Option<(&K, &V)> {
+    self.nodes.get(node).map(|n| (&n.key, &n.value))
+  }
+
+  /// Returns the node following `node`, or the head if `NIL`, at `level`.
+  #[inline]
+  fn link(&self, node: usize, level: usize) -> usize {
+    match node {
+      NIL => self.head[level],
+      node => self.links[self.nodes[node].tower + level],
+    }
+  }
+
+  #[inline]
+  fn set_link(&mut self, node: usize, level: usize, next: usize) {
+    match node {
+      NIL => self.head[level] = next,
+      node => {
+        let tower = self.nodes[node].tower;
+        self.links[tower + level] = next;
+      }
+    }
+  }
+
+  /// Returns the last node whose key is `before` the target, or `NIL` if there
+  /// is none, recording the last such node of every level in `preds`.
+  fn seek<F>(&self, mut before: F, mut preds: Option<&mut [usize; MAX_HEIGHT]>) -> usize
+  where
+    F: FnMut(&K) -> bool,
+  {
+    let mut node = NIL;
+    for level in (0..self.height).rev() {
+      loop {
+        let next = self.link(node, level);
+        if next != NIL && before(&self.nodes[next].key) {
+          node = next;
+        } else {
+          break;
+        }
+      }
+      if let Some(preds) = preds.as_mut() {
+        preds[level] = node;
+      }
+    }
+    node
+  }
+
+  /// Returns the last node below `bound`, i.e. outside a range starting at it.
+  #[inline]
+  fn seek_below<Q>(&self, bound: Bound<&Q>) -> usize
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    match bound {
+      Bound::Included(q) => self.seek(|k| k.compare(q) == Ordering::Less, None),
+      Bound::Excluded(q) => self.seek(|k| k.compare(q) != Ordering::Greater, None),
+      Bound::Unbounded => NIL,
+    }
+  }
+
+  /// Returns the last node through `bound`, i.e. inside a range ending at it.
+  #[inline]
+  fn seek_through<Q>(&self, bound: Bound<&Q>) -> usize
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    match bound {
+      Bound::Included(q) => self.seek(|k| k.compare(q) != Ordering::Greater, None),
+      Bound::Excluded(q) => self.seek(|k| k.compare(q) == Ordering::Less, None),
+      Bound::Unbounded => self.seek(|_| true, None),
+    }
+  }
+
+  /// Returns the node with a key equivalent to `key`, or `NIL`.
+  #[inline]
+  fn find<Q>(&self, key: &Q) -> usize
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    let next = self.link(self.seek_below(Bound::Included(key)), 0);
+    match next {
+      NIL => NIL,
+      next if self.nodes[next].key.equivalent(key) => next,
+      _ => NIL,
+    }
+  }
+
+  /// Returns the height of a new tower, which is `n` with a probability of
+  /// `(1/4)^(n-1) * 3/4`.
+  fn random_height(&mut self) -> usize {
+    let mut height = 1;
+    while height < MAX_HEIGHT && self.next_random() % 4 == 0 {
+      height += 1;
+    }
+    height
+  }
+
+  /// A xorshift generator, which is plenty for balancing the towers.
+  #[inline]
+  fn next_random(&mut self) -> u32 {
+    let mut x = self.seed;
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    self.seed = x;
+    x
+  }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SkipList<K, V> {
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    let mut list = Self::new();
+    list.extend(iter);
+    list
+  }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for SkipList<K, V> {
+  fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+    for (k, v) in iter {
+      self.insert(k, v);
+    }
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipList<K, V> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = Iter<'a, K, V>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+/// An iterator over the entries of a [`SkipList`], in key order.
+pub struct Iter<'a, K, V> {
+  list: &'a SkipList<K, V>,
+  node: usize,
+  end: usize,
+}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      list: self.list,
+      node: self.node,
+      end: self.end,
+    }
+  }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Iter<'_, K, V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list().entries(self.clone()).finish()
+  }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+  type Item = (&'a K, &'a V);
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.node == self.end {
+      return None;
+    }
+    let node = &self.list.nodes[self.node];
+    self.node = self.list.links[node.tower];
+    Some((&node.key, &node.value))
+  }
+}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+  use core::ops::Bound;
+  use std::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+  };
+
+  use super::SkipList;
+  use crate::tests::{bounds, contains, points, ranges, xorshift};
+
+  /// A deterministic sequence of keys in `-2..=8`, with many repeats.
+  fn keys() -> impl Iterator<Item = i32> {
+    xorshift().take(60).map(|x| (x % 11) as i32 - 2)
+  }
+
+  fn check(list: &SkipList<i32, usize>, model: &BTreeMap<i32, usize>) {
+    assert_eq!(list.len(), model.len());
+    assert!(list.iter().eq(model.iter()));
+    assert_eq!(list.first(), model.iter().next());
+    assert_eq!(list.last(), model.iter().next_back());
+    for k in points() {
+      assert_eq!(list.get_key_value(&k), model.get_key_value(&k));
+      assert_eq!(list.contains_key(&k), model.contains_key(&k));
+    }
+    for range in ranges() {
+      let expected: Vec<_> = model
+        .iter()
+        .filter(|(k, _)| contains(&range, **k))
+        .collect();
+      let entries: Vec<_> = list.range(&range).collect();
+      assert_eq!(entries, expected, "{:?}", range);
+    }
+    for bound in bounds() {
+      let bound = bound.as_ref();
+      assert_eq!(
+        list.lower_bound(bound),
+        model.range((bound, Bound::Unbounded)).next(),
+        "{:?}",
+        bound
+      );
+      assert_eq!(
+        list.upper_bound(bound),
+        model.range((Bound::Unbounded, bound)).next_back(),
+        "{:?}",
+        bound
+      );
+    }
+  }
+
+  #[test]
+  fn matches_btree_map() {
+    let mut list = SkipList::new();
+    let mut model = BTreeMap::new();
+    check(&list, &model);
+    for (i, k) in keys().enumerate() {
+      assert_eq!(list.insert(k, i), model.insert(k, i));
+      check(&list, &model);
+    }
+  }
+
+  #[test]
+  fn empty_list() {
+    let list: SkipList<i32, ()> = SkipList::new();
+    assert!(list.is_empty());
+    assert_eq!(list.first(), None);
+    assert_eq!(list.last(), None);
+    assert_eq!(list.get(&0), None);
+    assert_eq!(list.lower_bound::<i32>(Bound::Unbounded), None);
+    assert_eq!(list.upper_bound::<i32>(Bound::Unbounded), None);
+    assert_eq!(list.range::<i32, _>(&(..)).next(), None);
+  }
+
+  #[test]
+  fn insert_replaces_value() {
+    let mut list: SkipList<String, u32> = SkipList::new();
+    assert_eq!(list.insert(String::from("a"), 1), None);
+    assert_eq!(list.insert(String::from("a"), 2), Some(1));
+    assert_eq!(list.len(), 1);
+    assert_eq!(list.get("a"), Some(&2));
+    *list.get_mut("a").unwrap() += 1;
+    assert_eq!(list.get("a"), Some(&3));
+  }
+
+  #[test]
+  fn clear_and_reuse() {
+    let mut list: SkipList<String, usize> = (0..100).map(|i| (i.to_string(), i)).collect();
+    list.clear();
+    assert!(list.is_empty());
+    assert_eq!(list.get("1"), None);
+    assert_eq!(list.iter().next(), None);
+
+    list.extend((0..10).rev().map(|i| (i.to_string(), i)));
+    assert_eq!(list.len(), 10);
+    assert!(list.iter().map(|(_, &v)| v).eq(0..10));
+    assert_eq!(list.get("7"), Some(&7));
+  }
+}
@@ -0,0 +1,231 @@
+use core::{
+  cmp::Ordering,
+  ops::{Bound, Range},
+};
+
+use super::{Comparable, ComparableRangeBounds};
+
+/// Extension methods for slices of `K` sorted by their [`Comparable`]
+/// ordering, which can be searched with any `Q` where `K: Comparable<Q>`.
+///
+/// Like the searches of `core`, the results are unspecified if the slice is
+/// not sorted.
+pub trait SliceExt<K> {
+  /// Binary searches this slice for `key`, like
+  /// [`binary_search`](slice::binary_search).
+  ///
+  /// Returns `Ok` with the index of a matching element, or `Err` with the
+  /// index where a matching element could be inserted while maintaining
+  /// sorted order.
+  fn binary_search_comparable<Q>(&self, key: &Q) -> Result<usize, usize>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized;
+
+  /// Returns the index of the first element which is not less than `key`.
+  fn lower_bound<Q>(&self, key: &Q) -> usize
+  where
+    K: Comparable<Q>,
+    Q: ?Sized;
+
+  /// Returns the index of the first element which is greater than `key`.
+  fn upper_bound<Q>(&self, key: &Q) -> usize
+  where
+    K: Comparable<Q>,
+    Q: ?Sized;
+
+  /// Returns the range of indices of the elements which compare equal to
+  /// `key`.
+  fn equal_range<Q>(&self, key: &Q) -> Range<usize>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized;
+
+  /// Returns the range of indices of the elements contained in `range`, in
+  /// `O(log n)`.
+  ///
+  /// The bounds are honored exactly like
+  /// [`compare_contains`](ComparableRangeBounds::compare_contains) does, and an
+  /// empty range of indices is returned if the start of `range` is after its
+  /// end.
+  fn range_indices<Q, R>(&self, range: &R) -> Range<usize>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + ComparableRangeBounds<Q>;
+
+  /// Returns the sub-slice of the elements contained in `range`, in
+  /// `O(log n)`.
+  ///
+  /// See [`range_indices`](SliceExt::range_indices) for details.
+  fn range_slice<Q, R>(&self, range: &R) -> &[K]
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + ComparableRangeBounds<Q>;
+}
+
+impl<K> SliceExt<K> for [K] {
+  #[inline]
+  fn binary_search_comparable<Q>(&self, key: &Q) -> Result<usize, usize>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.binary_search_by(|k| k.compare(key))
+  }
+
+  #[inline]
+  fn lower_bound<Q>(&self, key: &Q) -> usize
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.partition_point(|k| k.compare(key) == Ordering::Less)
+  }
+
+  #[inline]
+  fn upper_bound<Q>(&self, key: &Q) -> usize
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.partition_point(|k| k.compare(key) != Ordering::Greater)
+  }
+
+  #[inline]
+  fn equal_range<Q>(&self, key: &Q) -> Range<usize>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    let start = self.lower_bound(key);
+    let end = start + self[start..].upper_bound(key);
+    start..end
+  }
+
+  #[inline]
+  fn range_indices<Q, R>(&self, range: &R) -> Range<usize>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + ComparableRangeBounds<Q>,
+  {
+    let start = match range.start_bound() {
+      Bound::Included(start) => self.lower_bound(start),
+      Bound::Excluded(start) => self.upper_bound(start),
+      Bound::Unbounded => 0,
+    };
+    let rest = &self[start..];
+    let end = start
+      + match range.end_bound() {
+        Bound::Included(end) => rest.upper_bound(end),
+        Bound::Excluded(end) => rest.lower_bound(end),
+        Bound::Unbounded => rest.len(),
+      };
+    start..end
+  }
+
+  #[inline]
+  fn range_slice<Q, R>(&self, range: &R) -> &[K]
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + ComparableRangeBounds<Q>,
+  {
+    &self[self.range_indices(range)]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::ops::Bound;
+  use std::{string::String, vec::Vec};
+
+  use super::SliceExt;
+  use crate::ComparableRangeBounds;
+
+  fn keys() -> Vec<String> {
+    ["a", "b", "b", "b", "d"]
+      .iter()
+      .map(|s| String::from(*s))
+      .collect()
+  }
+
+  #[test]
+  fn binary_search() {
+    let keys = keys();
+    assert_eq!(keys.binary_search_comparable("a"), Ok(0));
+    assert_eq!(keys.binary_search_comparable("d"), Ok(4));
+    assert!(keys.binary_search_comparable("b").is_ok());
+    assert_eq!(keys.binary_search_comparable(""), Err(0));
+    assert_eq!(keys.binary_search_comparable("c"), Err(4));
+    assert_eq!(keys.binary_search_comparable("e"), Err(5));
+  }
+
+  #[test]
+  fn bounds_and_equal_range() {
+    let keys = keys();
+    for (query, lower, upper) in [
+      ("", 0, 0),
+      ("a", 0, 1),
+      ("b", 1, 4),
+      ("c", 4, 4),
+      ("d", 4, 5),
+      ("e", 5, 5),
+    ] {
+      assert_eq!(keys.lower_bound(query), lower, "{query}");
+      assert_eq!(keys.upper_bound(query), upper, "{query}");
+      assert_eq!(keys.equal_range(query), lower..upper, "{query}");
+    }
+
+    let empty: [String; 0] = [];
+    assert_eq!(empty.lower_bound("a"), 0);
+    assert_eq!(empty.upper_bound("a"), 0);
+    assert_eq!(empty.equal_range("a"), 0..0);
+  }
+
+  fn bounds() -> Vec<Bound<u32>> {
+    let mut bounds = Vec::from([Bound::Unbounded]);
+    for i in 0..7 {
+      bounds.push(Bound::Included(i));
+      bounds.push(Bound::Excluded(i));
+    }
+    bounds
+  }
+
+  #[test]
+  fn range_indices_matches_filter() {
+    let keys = [1u32, 2, 2, 3, 5];
+    for &start in &bounds() {
+      for &end in &bounds() {
+        let range = (start, end);
+        let expected: Vec<u32> = keys
+          .iter()
+          .copied()
+          .filter(|k| range.compare_contains(k))
+          .collect();
+
+        let indices = keys.range_indices(&range);
+        assert_eq!(&keys[indices.clone()], &expected[..], "{:?}", range);
+        assert_eq!(keys.range_slice(&range), &expected[..], "{:?}", range);
+        // Empty results are still located at the start of the range.
+        assert!(indices.start <= indices.end, "{:?}", range);
+      }
+    }
+  }
+
+  #[test]
+  fn range_slice_with_borrowed_queries() {
+    use core::ops::Bound::*;
+
+    let keys = keys();
+    let slice = |range: (Bound<&str>, Bound<&str>)| keys.range_slice::<str, _>(&range);
+    assert_eq!(slice((Included("b"), Excluded("d"))), &keys[1..4]);
+    assert_eq!(slice((Included("b"), Included("d"))), &keys[1..]);
+    assert_eq!(slice((Excluded("a"), Included("c"))), &keys[1..4]);
+    assert_eq!(slice((Unbounded, Excluded("b"))), &keys[..1]);
+    assert_eq!(slice((Included("c"), Unbounded)), &keys[4..]);
+    assert!(slice((Included("d"), Excluded("b"))).is_empty());
+  }
+}
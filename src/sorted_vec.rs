@@ -0,0 +1,910 @@
+//! A flat map and set, which keep their entries sorted in vectors and can be
+//! queried with any `Q` where `K: Comparable<Q>`.
+//!
+//! Lookups are `O(log n)`, while insertions and removals shift the entries
+//! after them, which is `O(n)`, but fast for the small collections these are
+//! meant for.
+
+use alloc::vec::{self, Vec};
+use core::{
+  fmt,
+  iter::{FromIterator, FusedIterator, Zip},
+  slice,
+};
+
+use super::{Comparable, ComparableRangeBounds, Equivalent, SliceExt};
+
+/// A map backed by a vector of keys sorted by their `Ord` implementation.
+///
+/// Every lookup accepts any `Q` where `K: Comparable<Q>`, and
+/// [`range`](SortedVecMap::range) any `R: ComparableRangeBounds<Q>`. The
+/// `Comparable<Q>` implementation must be consistent with the `Ord`
+/// implementation of `K`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SortedVecMap<K, V> {
+  keys: Vec<K>,
+  values: Vec<V>,
+}
+
+impl<K, V> Default for SortedVecMap<K, V> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for SortedVecMap<K, V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<K, V> SortedVecMap<K, V> {
+  /// Creates an empty map.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      keys: Vec::new(),
+      values: Vec::new(),
+    }
+  }
+
+  /// Creates an empty map with space for at least `capacity` entries.
+  #[inline]
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      keys: Vec::with_capacity(capacity),
+      values: Vec::with_capacity(capacity),
+    }
+  }
+
+  /// Returns the number of entries in the map.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.keys.len()
+  }
+
+  /// Returns `true` if the map contains no entries.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.keys.is_empty()
+  }
+
+  /// Removes all the entries of the map.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.keys.clear();
+    self.values.clear();
+  }
+
+  /// Returns the sorted keys of the map, as a slice.
+  #[inline]
+  pub fn keys(&self) -> &[K] {
+    &self.keys
+  }
+
+  /// Returns the values of the map, in the order of their keys, as a slice.
+  #[inline]
+  pub fn values(&self) -> &[V] {
+    &self.values
+  }
+
+  /// Returns the values of the map, in the order of their keys, as a mutable
+  /// slice.
+  #[inline]
+  pub fn values_mut(&mut self) -> &mut [V] {
+    &mut self.values
+  }
+
+  /// Returns an iterator over the entries of the map, in key order.
+  #[inline]
+  pub fn iter(&self) -> Iter<'_, K, V> {
+    Iter {
+      inner: self.keys.iter().zip(self.values.iter()),
+    }
+  }
+
+  /// Returns an iterator over the entries of the map, in key order, with
+  /// mutable references to the values.
+  #[inline]
+  pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+    IterMut {
+      inner: self.keys.iter().zip(self.values.iter_mut()),
+    }
+  }
+
+  /// Returns the entry with the least key.
+  #[inline]
+  pub fn first_key_value(&self) -> Option<(&K, &V)> {
+    Some((self.keys.first()?, self.values.first()?))
+  }
+
+  /// Returns the entry with the greatest key.
+  #[inline]
+  pub fn last_key_value(&self) -> Option<(&K, &V)> {
+    Some((self.keys.last()?, self.values.last()?))
+  }
+
+  /// Returns the entry with a key equivalent to `key`.
+  #[inline]
+  pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    let index = self.keys.binary_search_comparable(key).ok()?;
+    Some((&self.keys[index], &self.values[index]))
+  }
+
+  /// Returns a reference to the value of the key equivalent to `key`.
+  #[inline]
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    let index = self.keys.binary_search_comparable(key).ok()?;
+    Some(&self.values[index])
+  }
+
+  /// Returns a mutable reference to the value of the key equivalent to `key`.
+  #[inline]
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    let index = self.keys.binary_search_comparable(key).ok()?;
+    Some(&mut self.values[index])
+  }
+
+  /// Returns `true` if the map contains a key equivalent to `key`.
+  #[inline]
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.keys.binary_search_comparable(key).is_ok()
+  }
+
+  /// Removes the entry with a key equivalent to `key`, returning its value.
+  #[inline]
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.remove_entry(key).map(|(_, v)| v)
+  }
+
+  /// Removes the entry with a key equivalent to `key`, returning it.
+  #[inline]
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    let index = self.keys.binary_search_comparable(key).ok()?;
+    Some((self.keys.remove(index), self.values.remove(index)))
+  }
+
+  /// Returns an iterator over the entries whose keys are contained in `range`,
+  /// in key order.
+  #[inline]
+  pub fn range<Q, R>(&self, range: &R) -> Iter<'_, K, V>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + ComparableRangeBounds<Q>,
+  {
+    let indices = self.keys.range_indices(range);
+    Iter {
+      inner: self.keys[indices.clone()]
+        .iter()
+        .zip(self.values[indices].iter()),
+    }
+  }
+
+  /// Returns an iterator over the entries whose keys are contained in `range`,
+  /// in key order, with mutable references to the values.
+  #[inline]
+  pub fn range_mut<Q, R>(&mut self, range: &R) -> IterMut<'_, K, V>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + ComparableRangeBounds<Q>,
+  {
+    let indices = self.keys.range_indices(range);
+    IterMut {
+      inner: self.keys[indices.clone()]
+        .iter()
+        .zip(self.values[indices].iter_mut()),
+    }
+  }
+
+  /// Returns the entry of the key equivalent to `key`, for in-place
+  /// manipulation.
+  ///
+  /// Inserting into a vacant entry creates the key with `Q::to_owned`, which
+  /// must be ordered consistently with `key`.
+  #[inline]
+  pub fn entry<'q, Q>(&mut self, key: &'q Q) -> Entry<'_, 'q, K, V, Q>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    match self.keys.binary_search_comparable(key) {
+      Ok(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+      Err(index) => Entry::Vacant(VacantEntry {
+        map: self,
+        index,
+        key,
+      }),
+    }
+  }
+
+  /// Inserts a key-value pair into the map, returning the previous value of
+  /// the key, if any.
+  ///
+  /// As with the standard maps, the key itself is not updated if it was
+  /// already present.
+  #[inline]
+  pub fn insert(&mut self, key: K, value: V) -> Option<V>
+  where
+    K: Ord,
+  {
+    match self.keys.binary_search(&key) {
+      Ok(index) => Some(core::mem::replace(&mut self.values[index], value)),
+      Err(index) => {
+        self.keys.insert(index, key);
+        self.values.insert(index, value);
+        None
+      }
+    }
+  }
+
+  /// Retains only the entries for which `f` returns `true`.
+  pub fn retain<F>(&mut self, mut f: F)
+  where
+    F: FnMut(&K, &mut V) -> bool,
+  {
+    // Moves each kept entry down to `kept`, behind the ones kept before it, so
+    // that both vectors stay sorted and aligned.
+    let mut kept = 0;
+    for i in 0..self.keys.len() {
+      if f(&self.keys[i], &mut self.values[i]) {
+        self.keys.swap(kept, i);
+        self.values.swap(kept, i);
+        kept += 1;
+      }
+    }
+    self.keys.truncate(kept);
+    self.values.truncate(kept);
+  }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SortedVecMap<K, V> {
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    // Reversed, the stable sort puts the last value of each key first among
+    // its duplicates, which is the one `dedup_by` keeps, as `insert` would.
+    let mut entries: Vec<(K, V)> = iter.into_iter().collect();
+    entries.reverse();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries.dedup_by(|a, b| a.0 == b.0);
+    let (keys, values) = entries.into_iter().unzip();
+    Self { keys, values }
+  }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for SortedVecMap<K, V> {
+  fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+    for (k, v) in iter {
+      self.insert(k, v);
+    }
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a SortedVecMap<K, V> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = Iter<'a, K, V>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut SortedVecMap<K, V> {
+  type Item = (&'a K, &'a mut V);
+  type IntoIter = IterMut<'a, K, V>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+impl<K, V> IntoIterator for SortedVecMap<K, V> {
+  type Item = (K, V);
+  type IntoIter = IntoIter<K, V>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    IntoIter {
+      inner: self.keys.into_iter().zip(self.values),
+    }
+  }
+}
+
+macro_rules! delegate_iterator {
+  ($name:ident<$($lt:lifetime,)? $($param:ident),+> => $item:ty) => {
+    impl<$($lt,)? $($param),+> Iterator for $name<$($lt,)? $($param),+> {
+      type Item = $item;
+
+      #[inline]
+      fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+      }
+
+      #[inline]
+      fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+      }
+    }
+
+    impl<$($lt,)? $($param),+> DoubleEndedIterator for $name<$($lt,)? $($param),+> {
+      #[inline]
+      fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+      }
+    }
+
+    impl<$($lt,)? $($param),+> ExactSizeIterator for $name<$($lt,)? $($param),+> {}
+
+    impl<$($lt,)? $($param),+> FusedIterator for $name<$($lt,)? $($param),+> {}
+  };
+}
+
+/// An iterator over the entries of a [`SortedVecMap`], in key order.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, K, V> {
+  inner: Zip<slice::Iter<'a, K>, slice::Iter<'a, V>>,
+}
+
+delegate_iterator!(Iter<'a, K, V> => (&'a K, &'a V));
+
+/// An iterator over the entries of a [`SortedVecMap`], in key order, with
+/// mutable references to the values.
+#[derive(Debug)]
+pub struct IterMut<'a, K, V> {
+  inner: Zip<slice::Iter<'a, K>, slice::IterMut<'a, V>>,
+}
+
+delegate_iterator!(IterMut<'a, K, V> => (&'a K, &'a mut V));
+
+/// An owning iterator over the entries of a [`SortedVecMap`], in key order.
+#[derive(Debug)]
+pub struct IntoIter<K, V> {
+  inner: Zip<vec::IntoIter<K>, vec::IntoIter<V>>,
+}
+
+delegate_iterator!(IntoIter<K, V> => (K, V));
+
+/// A view into a single entry of a [`SortedVecMap`], which may either be
+/// vacant or occupied.
+pub enum Entry<'a, 'q, K, V, Q: ?Sized> {
+  /// An occupied entry.
+  Occupied(OccupiedEntry<'a, K, V>),
+  /// A vacant entry.
+  Vacant(VacantEntry<'a, 'q, K, V, Q>),
+}
+
+impl<'a, K, V, Q: ?Sized> Entry<'a, '_, K, V, Q> {
+  /// Ensures a value is in the entry by inserting `default` if empty, and
+  /// returns a mutable reference to the value.
+  #[inline]
+  pub fn or_insert(self, default: V) -> &'a mut V
+  where
+    K: Equivalent<Q>,
+    Q: alloc::borrow::ToOwned<Owned = K>,
+  {
+    match self {
+      Entry::Occupied(entry) => entry.into_mut(),
+      Entry::Vacant(entry) => entry.insert(default),
+    }
+  }
+
+  /// Ensures a value is in the entry by inserting the result of `default` if
+  /// empty, and returns a mutable reference to the value.
+  #[inline]
+  pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+  where
+    K: Equivalent<Q>,
+    Q: alloc::borrow::ToOwned<Owned = K>,
+    F: FnOnce() -> V,
+  {
+    match self {
+      Entry::Occupied(entry) => entry.into_mut(),
+      Entry::Vacant(entry) => entry.insert(default()),
+    }
+  }
+
+  /// Ensures a value is in the entry by inserting `V::default()` if empty, and
+  /// returns a mutable reference to the value.
+  #[inline]
+  pub fn or_default(self) -> &'a mut V
+  where
+    K: Equivalent<Q>,
+    Q: alloc::borrow::ToOwned<Owned = K>,
+    V: Default,
+  {
+    self.or_insert_with(V::default)
+  }
+
+  /// Calls `f` with the value of an occupied entry, before any potential
+  /// insert into the map.
+  #[inline]
+  pub fn and_modify<F>(mut self, f: F) -> Self
+  where
+    F: FnOnce(&mut V),
+  {
+    if let Entry::Occupied(entry) = &mut self {
+      f(entry.get_mut());
+    }
+    self
+  }
+}
+
+/// A view into an occupied entry of a [`SortedVecMap`].
+pub struct OccupiedEntry<'a, K, V> {
+  map: &'a mut SortedVecMap<K, V>,
+  index: usize,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+  /// Returns the key of the entry.
+  #[inline]
+  pub fn key(&self) -> &K {
+    &self.map.keys[self.index]
+  }
+
+  /// Returns a reference to the value of the entry.
+  #[inline]
+  pub fn get(&self) -> &V {
+    &self.map.values[self.index]
+  }
+
+  /// Returns a mutable reference to the value of the entry.
+  #[inline]
+  pub fn get_mut(&mut self) -> &mut V {
+    &mut self.map.values[self.index]
+  }
+
+  /// Converts the entry into a mutable reference to its value, with the
+  /// lifetime of the map.
+  #[inline]
+  pub fn into_mut(self) -> &'a mut V {
+    &mut self.map.values[self.index]
+  }
+
+  /// Sets the value of the entry, returning the old value.
+  #[inline]
+  pub fn insert(&mut self, value: V) -> V {
+    core::mem::replace(self.get_mut(), value)
+  }
+
+  /// Removes the entry from the map, returning its value.
+  #[inline]
+  pub fn remove(self) -> V {
+    self.remove_entry().1
+  }
+
+  /// Removes the entry from the map, returning it.
+  #[inline]
+  pub fn remove_entry(self) -> (K, V) {
+    (
+      self.map.keys.remove(self.index),
+      self.map.values.remove(self.index),
+    )
+  }
+}
+
+/// A view into a vacant entry of a [`SortedVecMap`].
+pub struct VacantEntry<'a, 'q, K, V, Q: ?Sized> {
+  map: &'a mut SortedVecMap<K, V>,
+  index: usize,
+  key: &'q Q,
+}
+
+impl<'a, 'q, K, V, Q: ?Sized> VacantEntry<'a, 'q, K, V, Q> {
+  /// Returns the key the entry was looked up with.
+  #[inline]
+  pub fn key(&self) -> &'q Q {
+    self.key
+  }
+
+  /// Inserts the entry into the map, with the key created by
+  /// `Q::to_owned`, and returns a mutable reference to its value.
+  #[inline]
+  pub fn insert(self, value: V) -> &'a mut V
+  where
+    K: Equivalent<Q>,
+    Q: alloc::borrow::ToOwned<Owned = K>,
+  {
+    let key = self.key.to_owned();
+    self.insert_with_key(key, value)
+  }
+
+  /// Inserts the entry into the map with the given key, which must be
+  /// equivalent to the key the entry was looked up with, and returns a
+  /// mutable reference to its value.
+  ///
+  /// # Panics
+  ///
+  /// In debug builds, panics if `key` is not equivalent to the key the entry
+  /// was looked up with.
+  #[inline]
+  pub fn insert_with_key(self, key: K, value: V) -> &'a mut V
+  where
+    K: Equivalent<Q>,
+  {
+    debug_assert!(
+      key.equivalent(self.key),
+      "the key is not equivalent to the query it was looked up with"
+    );
+
+    self.map.keys.insert(self.index, key);
+    self.map.values.insert(self.index, value);
+    &mut self.map.values[self.index]
+  }
+}
+
+/// A set backed by a vector of keys sorted by their `Ord` implementation.
+///
+/// Every lookup accepts any `Q` where `K: Comparable<Q>`, and
+/// [`range`](SortedVecSet::range) any `R: ComparableRangeBounds<Q>`. The
+/// `Comparable<Q>` implementation must be consistent with the `Ord`
+/// implementation of `K`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SortedVecSet<K> {
+  keys: Vec<K>,
+}
+
+impl<K> Default for SortedVecSet<K> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K: fmt::Debug> fmt::Debug for SortedVecSet<K> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
+
+impl<K> SortedVecSet<K> {
+  /// Creates an empty set.
+  #[inline]
+  pub fn new() -> Self {
+    Self { keys: Vec::new() }
+  }
+
+  /// Creates an empty set with space for at least `capacity` keys.
+  #[inline]
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      keys: Vec::with_capacity(capacity),
+    }
+  }
+
+  /// Returns the number of keys in the set.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.keys.len()
+  }
+
+  /// Returns `true` if the set contains no keys.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.keys.is_empty()
+  }
+
+  /// Removes all the keys of the set.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.keys.clear();
+  }
+
+  /// Returns the sorted keys of the set, as a slice.
+  #[inline]
+  pub fn as_slice(&self) -> &[K] {
+    &self.keys
+  }
+
+  /// Returns an iterator over the keys of the set, in order.
+  #[inline]
+  pub fn iter(&self) -> slice::Iter<'_, K> {
+    self.keys.iter()
+  }
+
+  /// Returns the least key.
+  #[inline]
+  pub fn first(&self) -> Option<&K> {
+    self.keys.first()
+  }
+
+  /// Returns the greatest key.
+  #[inline]
+  pub fn last(&self) -> Option<&K> {
+    self.keys.last()
+  }
+
+  /// Returns the key equivalent to `key`.
+  #[inline]
+  pub fn get<Q>(&self, key: &Q) -> Option<&K>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    let index = self.keys.binary_search_comparable(key).ok()?;
+    Some(&self.keys[index])
+  }
+
+  /// Returns `true` if the set contains a key equivalent to `key`.
+  #[inline]
+  pub fn contains<Q>(&self, key: &Q) -> bool
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.keys.binary_search_comparable(key).is_ok()
+  }
+
+  /// Removes the key equivalent to `key`, returning `true` if it was present.
+  #[inline]
+  pub fn remove<Q>(&mut self, key: &Q) -> bool
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    self.take(key).is_some()
+  }
+
+  /// Removes the key equivalent to `key`, returning it.
+  #[inline]
+  pub fn take<Q>(&mut self, key: &Q) -> Option<K>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+  {
+    let index = self.keys.binary_search_comparable(key).ok()?;
+    Some(self.keys.remove(index))
+  }
+
+  /// Returns an iterator over the keys contained in `range`, in order.
+  #[inline]
+  pub fn range<Q, R>(&self, range: &R) -> slice::Iter<'_, K>
+  where
+    K: Comparable<Q>,
+    Q: ?Sized,
+    R: ?Sized + ComparableRangeBounds<Q>,
+  {
+    self.keys.range_slice(range).iter()
+  }
+
+  /// Inserts a key into the set, returning `true` if it was not present.
+  ///
+  /// As with the standard sets, the key is not updated if it was already
+  /// present.
+  #[inline]
+  pub fn insert(&mut self, key: K) -> bool
+  where
+    K: Ord,
+  {
+    match self.keys.binary_search(&key) {
+      Ok(_) => false,
+      Err(index) => {
+        self.keys.insert(index, key);
+        true
+      }
+    }
+  }
+
+  /// Retains only the keys for which `f` returns `true`.
+  #[inline]
+  pub fn retain<F>(&mut self, f: F)
+  where
+    F: FnMut(&K) -> bool,
+  {
+    self.keys.retain(f);
+  }
+}
+
+impl<K: Ord> FromIterator<K> for SortedVecSet<K> {
+  fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+    let mut keys: Vec<K> = iter.into_iter().collect();
+    keys.sort();
+    keys.dedup();
+    Self { keys }
+  }
+}
+
+impl<K: Ord> Extend<K> for SortedVecSet<K> {
+  fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+    for k in iter {
+      self.insert(k);
+    }
+  }
+}
+
+impl<'a, K> IntoIterator for &'a SortedVecSet<K> {
+  type Item = &'a K;
+  type IntoIter = slice::Iter<'a, K>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<K> IntoIterator for SortedVecSet<K> {
+  type Item = K;
+  type IntoIter = vec::IntoIter<K>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.keys.into_iter()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::ops::Bound;
+  use std::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+  };
+
+  use super::{Entry, SortedVecMap, SortedVecSet};
+  use crate::tests::{ranges, xorshift};
+
+  /// A deterministic sequence of small keys, with many repeats.
+  fn keys() -> impl Iterator<Item = u32> {
+    xorshift().take(500).map(|x| x % 64)
+  }
+
+  #[test]
+  fn matches_btree_map() {
+    let mut map = SortedVecMap::new();
+    let mut model = BTreeMap::new();
+    for (i, k) in keys().enumerate() {
+      let key = k.to_string();
+      match i % 3 {
+        0 | 1 => assert_eq!(map.insert(key.clone(), i), model.insert(key.clone(), i)),
+        _ => assert_eq!(map.remove(key.as_str()), model.remove(&key)),
+      }
+      assert_eq!(map.get(key.as_str()), model.get(&key));
+      assert_eq!(map.len(), model.len());
+    }
+
+    assert!(map.iter().eq(model.iter()));
+    assert_eq!(map.first_key_value(), model.iter().next());
+    assert_eq!(map.last_key_value(), model.iter().next_back());
+    for k in 0..64 {
+      let key = k.to_string();
+      assert_eq!(map.get_key_value(key.as_str()), model.get_key_value(&key));
+      assert_eq!(map.contains_key(key.as_str()), model.contains_key(&key));
+    }
+  }
+
+  #[test]
+  fn collect_keeps_last_value() {
+    let entries: Vec<_> = keys().enumerate().map(|(i, k)| (k, i)).collect();
+    let map: SortedVecMap<_, _> = entries.iter().copied().collect();
+    let model: BTreeMap<_, _> = entries.into_iter().collect();
+    assert!(map.iter().eq(model.iter()));
+  }
+
+  #[test]
+  fn range_matches_btree_map() {
+    let map: SortedVecMap<i32, ()> = (-2..=8).map(|k| (k, ())).collect();
+    let model: BTreeMap<i32, ()> = map.iter().map(|(&k, &v)| (k, v)).collect();
+    for range in ranges() {
+      let expected: Vec<_> = model
+        .keys()
+        .filter(|k| crate::tests::contains(&range, **k))
+        .collect();
+      let keys: Vec<_> = map.range(&range).map(|(k, _)| k).collect();
+      assert_eq!(keys, expected, "{:?}", range);
+    }
+  }
+
+  #[test]
+  fn entry_api() {
+    let mut map: SortedVecMap<String, u32> = SortedVecMap::new();
+    *map.entry("b").or_insert(0) += 1;
+    *map.entry("b").or_insert(0) += 1;
+    *map.entry("a").or_default() += 5;
+    map.entry("c").and_modify(|v| *v += 1).or_insert_with(|| 7);
+    map.entry("c").and_modify(|v| *v += 1).or_insert_with(|| 7);
+    assert!(map
+      .iter()
+      .map(|(k, &v)| (k.as_str(), v))
+      .eq([("a", 5), ("b", 2), ("c", 8)]));
+
+    match map.entry("b") {
+      Entry::Occupied(mut entry) => {
+        assert_eq!(entry.key(), "b");
+        assert_eq!(entry.insert(10), 2);
+        assert_eq!(entry.remove_entry(), (String::from("b"), 10));
+      }
+      Entry::Vacant(_) => unreachable!(),
+    }
+    match map.entry("bb") {
+      Entry::Vacant(entry) => {
+        assert_eq!(entry.key(), "bb");
+        *entry.insert_with_key(String::from("bb"), 1) += 1;
+      }
+      Entry::Occupied(_) => unreachable!(),
+    }
+    assert_eq!(map.keys(), ["a", "bb", "c"]);
+    assert_eq!(map.values(), [5, 2, 8]);
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "not equivalent")]
+  fn insert_with_mismatched_key() {
+    let mut map: SortedVecMap<String, u32> = SortedVecMap::new();
+    if let Entry::Vacant(entry) = map.entry("b") {
+      entry.insert_with_key(String::from("z"), 0);
+    }
+  }
+
+  #[test]
+  fn retain_and_clear() {
+    let mut map: SortedVecMap<u32, u32> = (0..10).map(|k| (k, k * 10)).collect();
+    map.retain(|k, v| {
+      *v += 1;
+      k % 3 == 0
+    });
+    assert_eq!(map.keys(), [0, 3, 6, 9]);
+    assert_eq!(map.values(), [1, 31, 61, 91]);
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.get(&3), None);
+  }
+
+  #[test]
+  fn set_matches_btree_set() {
+    let mut set = SortedVecSet::new();
+    let mut model = BTreeSet::new();
+    for (i, k) in keys().enumerate() {
+      if i % 3 == 2 {
+        assert_eq!(set.remove(&k), model.remove(&k));
+      } else {
+        assert_eq!(set.insert(k), model.insert(k));
+      }
+      assert_eq!(set.contains(&k), model.contains(&k));
+    }
+    assert!(set.iter().eq(model.iter()));
+    assert_eq!(set.first(), model.iter().next());
+    assert_eq!(set.last(), model.iter().next_back());
+    assert!(set
+      .range(&(Bound::Excluded(10), Bound::Included(40)))
+      .eq(model.range(11..=40)));
+
+    set.retain(|k| k % 2 == 0);
+    model.retain(|k| k % 2 == 0);
+    assert_eq!(
+      set.as_slice(),
+      &model.iter().copied().collect::<Vec<_>>()[..]
+    );
+    let first = *set.first().unwrap();
+    assert_eq!(set.take(&first), model.take(&first));
+    assert_eq!(set.first(), model.iter().next());
+  }
+}
@@ -0,0 +1,130 @@
+use core::cmp::Ordering;
+
+use super::{Comparable, Equivalent};
+
+/// A query chaining a primary and a secondary query, like
+/// [`Ordering::then_with`].
+///
+/// A key is compared to the primary query first, and only compared to the
+/// secondary query if that is `Equal`; it is equivalent to `Then(p, s)` if it
+/// is equivalent to both. The primary and secondary queries are typically
+/// [`Project`](crate::Project)s of different fields, which makes `Then` a
+/// multi-column index lookup; nest it to chain more columns.
+///
+/// Use [`Wildcard`] as the secondary query to match any secondary value.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Then<P, S>(pub P, pub S);
+
+impl<K, P, S> Equivalent<Then<P, S>> for K
+where
+  K: ?Sized + Equivalent<P> + Equivalent<S>,
+{
+  #[inline]
+  fn equivalent(&self, key: &Then<P, S>) -> bool {
+    Equivalent::<P>::equivalent(self, &key.0) && Equivalent::<S>::equivalent(self, &key.1)
+  }
+}
+
+impl<K, P, S> Comparable<Then<P, S>> for K
+where
+  K: ?Sized + Comparable<P> + Comparable<S>,
+{
+  #[inline]
+  fn compare(&self, key: &Then<P, S>) -> Ordering {
+    Comparable::<P>::compare(self, &key.0).then_with(|| Comparable::<S>::compare(self, &key.1))
+  }
+}
+
+/// A query which every key is equivalent to.
+///
+/// It makes partial queries out of the composite ones, e.g.
+/// `Then(primary, Wildcard)`, or `Composite((&name, &Wildcard))` to look up a
+/// `(String, u32)` key by its first element only. With
+/// [`SliceExt::equal_range`](crate::SliceExt::equal_range), such a query finds
+/// every key matching the specified columns.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Wildcard;
+
+impl<K: ?Sized> Equivalent<Wildcard> for K {
+  #[inline]
+  fn equivalent(&self, _: &Wildcard) -> bool {
+    true
+  }
+}
+
+impl<K: ?Sized> Comparable<Wildcard> for K {
+  #[inline]
+  fn compare(&self, _: &Wildcard) -> Ordering {
+    Ordering::Equal
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::cmp::Ordering;
+  use std::{string::String, vec::Vec};
+
+  use super::{Then, Wildcard};
+  use crate::{Comparable, Composite, Equivalent, Project, SliceExt};
+
+  struct Row {
+    table: String,
+    id: u32,
+  }
+
+  fn rows() -> Vec<Row> {
+    [("a", 1), ("a", 2), ("b", 1), ("b", 5), ("b", 7), ("c", 0)]
+      .iter()
+      .map(|&(table, id)| Row {
+        table: String::from(table),
+        id,
+      })
+      .collect()
+  }
+
+  #[test]
+  fn multi_column_lookup() {
+    let rows = rows();
+    let find = |table: &str, id: &u32| {
+      rows.binary_search_comparable(&Then(
+        Project::new(table, |r: &Row| r.table.as_str()),
+        Project::new(id, |r: &Row| &r.id),
+      ))
+    };
+    assert_eq!(find("b", &5), Ok(3));
+    assert_eq!(find("b", &6), Err(4));
+    assert_eq!(find("a", &9), Err(2));
+
+    let row = &rows[3];
+    let query = Then(
+      Project::new("b", |r: &Row| r.table.as_str()),
+      Project::new(&1, |r: &Row| &r.id),
+    );
+    assert!(!row.equivalent(&query));
+    assert_eq!(row.compare(&query), Ordering::Greater);
+  }
+
+  #[test]
+  fn wildcard_matches_every_secondary_value() {
+    let rows = rows();
+    let table = |table: &str| {
+      rows.equal_range(&Then(
+        Project::new(table, |r: &Row| r.table.as_str()),
+        Wildcard,
+      ))
+    };
+    assert_eq!(table("a"), 0..2);
+    assert_eq!(table("b"), 2..5);
+    assert_eq!(table("bb"), 5..5);
+
+    assert!(7.equivalent(&Wildcard));
+    assert_eq!("x".compare(&Wildcard), Ordering::Equal);
+  }
+
+  #[test]
+  fn wildcard_in_composite() {
+    let keys: Vec<(String, u32)> = rows().into_iter().map(|r| (r.table, r.id)).collect();
+    assert_eq!(keys.equal_range(&Composite(("b", &Wildcard))), 2..5);
+    assert_eq!(keys.equal_range(&Composite(("c", &Wildcard))), 5..6);
+  }
+}
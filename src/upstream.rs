@@ -0,0 +1,205 @@
+//! Adapters between this crate and the upstream [`equivalent`] crate, whose
+//! traits are implemented by the query instead of the key.
+//!
+//! - [`Flipped`] wraps a query `Q` of this crate, so that
+//!   `Flipped<Q>: equivalent::Equivalent<K>` whenever `K: Equivalent<Q>`,
+//!   e.g. to look up an `indexmap` or `hashbrown` map.
+//! - [`Unflipped`] wraps an upstream query `Q`, so that
+//!   `K: Equivalent<Unflipped<Q>>` whenever `Q: equivalent::Equivalent<K>`,
+//!   e.g. to look up a map built on this crate.
+//!
+//! Both wrappers hash like the query they wrap. They intentionally do not
+//! implement `Eq`, which would make them overlap with the blanket
+//! implementations of both crates.
+//!
+//! # Example
+//!
+//! ```
+//! use std::cmp::Ordering;
+//! use equivalent_flipped::{
+//!   upstream::{Flipped, Unflipped},
+//!   Comparable, Equivalent,
+//! };
+//!
+//! /// A lookup shaped like the upstream crate's APIs, e.g. `IndexMap::get_index_of`.
+//! fn upstream_position<K, Q>(keys: &[K], query: &Q) -> Option<usize>
+//! where
+//!   Q: ?Sized + equivalent::Equivalent<K>,
+//! {
+//!   keys.iter().position(|k| query.equivalent(k))
+//! }
+//!
+//! /// A lookup shaped like the APIs of maps built on this crate.
+//! fn position<K, Q>(keys: &[K], query: &Q) -> Option<usize>
+//! where
+//!   K: Equivalent<Q>,
+//!   Q: ?Sized,
+//! {
+//!   keys.iter().position(|k| k.equivalent(query))
+//! }
+//!
+//! let keys = vec![String::from("a"), String::from("b")];
+//!
+//! // This crate's orientation, used by an upstream API.
+//! assert_eq!(upstream_position(&keys, Flipped::from_ref("b")), Some(1));
+//! assert_eq!(
+//!   equivalent::Comparable::compare(Flipped::from_ref("a"), &keys[1]),
+//!   Ordering::Less,
+//! );
+//!
+//! // The upstream orientation, used by an API of this crate.
+//! assert_eq!(position(&keys, Unflipped::from_ref("b")), Some(1));
+//! assert_eq!(
+//!   Comparable::compare(&keys[1], Unflipped::from_ref("a")),
+//!   Ordering::Greater,
+//! );
+//! ```
+
+use core::{
+  cmp::Ordering,
+  hash::{Hash, Hasher},
+};
+
+use super::{Comparable, Equivalent};
+
+transparent_wrapper! {
+  /// Adapts a query of this crate for APIs requiring
+  /// `equivalent::Equivalent<K>` (or `equivalent::Comparable<K>`) on the query.
+  #[derive(Debug, Default, Clone, Copy)]
+  pub struct Flipped<Q: ?Sized>(pub Q);
+}
+
+impl<Q: ?Sized + Hash> Hash for Flipped<Q> {
+  #[inline]
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.0.hash(state)
+  }
+}
+
+impl<K, Q> equivalent::Equivalent<K> for Flipped<Q>
+where
+  K: ?Sized + Equivalent<Q>,
+  Q: ?Sized,
+{
+  #[inline]
+  fn equivalent(&self, key: &K) -> bool {
+    key.equivalent(&self.0)
+  }
+}
+
+impl<K, Q> equivalent::Comparable<K> for Flipped<Q>
+where
+  K: ?Sized + Comparable<Q>,
+  Q: ?Sized,
+{
+  #[inline]
+  fn compare(&self, key: &K) -> Ordering {
+    key.compare(&self.0).reverse()
+  }
+}
+
+transparent_wrapper! {
+  /// Adapts a query implementing `equivalent::Equivalent<K>` (or
+  /// `equivalent::Comparable<K>`) for APIs of this crate.
+  #[derive(Debug, Default, Clone, Copy)]
+  pub struct Unflipped<Q: ?Sized>(pub Q);
+}
+
+impl<Q: ?Sized + Hash> Hash for Unflipped<Q> {
+  #[inline]
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.0.hash(state)
+  }
+}
+
+impl<K, Q> Equivalent<Unflipped<Q>> for K
+where
+  K: ?Sized,
+  Q: ?Sized + equivalent::Equivalent<K>,
+{
+  #[inline]
+  fn equivalent(&self, key: &Unflipped<Q>) -> bool {
+    key.0.equivalent(self)
+  }
+}
+
+impl<K, Q> Comparable<Unflipped<Q>> for K
+where
+  K: ?Sized,
+  Q: ?Sized + equivalent::Comparable<K>,
+{
+  #[inline]
+  fn compare(&self, key: &Unflipped<Q>) -> Ordering {
+    key.0.compare(self).reverse()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::{cmp::Ordering, hash::BuildHasherDefault};
+  use std::{collections::hash_map::DefaultHasher, string::String, vec::Vec};
+
+  use hashbrown::{HashMap, HashTable};
+
+  use super::{Flipped, Unflipped};
+  use crate::{tests::hash, Comparable, Equivalent};
+
+  type Map<K, V> = HashMap<K, V, BuildHasherDefault<DefaultHasher>>;
+
+  fn keys() -> Vec<String> {
+    ["a", "b", "c"].iter().map(|s| String::from(*s)).collect()
+  }
+
+  #[test]
+  fn hashes_agree() {
+    for key in keys() {
+      assert_eq!(hash(Flipped::from_ref(key.as_str())), hash(&key));
+      assert_eq!(hash(Unflipped::from_ref(key.as_str())), hash(&key));
+    }
+  }
+
+  #[test]
+  fn flipped_looks_up_upstream_map() {
+    let map: Map<_, _> = keys().into_iter().zip(0..).collect();
+    assert_eq!(map.get(Flipped::from_ref("b")), Some(&1));
+    assert_eq!(map.get(Flipped::from_ref("c")), Some(&2));
+    assert_eq!(map.get(Flipped::from_ref("d")), None);
+
+    let keys = keys();
+    let search = |q: &str| {
+      keys.binary_search_by(|k| equivalent::Comparable::compare(Flipped::from_ref(q), k).reverse())
+    };
+    assert_eq!(search("a"), Ok(0));
+    assert_eq!(search("c"), Ok(2));
+    assert_eq!(search("bb"), Err(2));
+  }
+
+  #[test]
+  fn unflipped_looks_up_table() {
+    let mut table = HashTable::new();
+    for key in keys() {
+      table.insert_unique(hash(&key), key, hash);
+    }
+
+    let get = |q: &str| {
+      table
+        .find(hash(Unflipped::from_ref(q)), |k| {
+          k.equivalent(Unflipped::from_ref(q))
+        })
+        .map(String::as_str)
+    };
+    assert_eq!(get("b"), Some("b"));
+    assert_eq!(get("c"), Some("c"));
+    assert_eq!(get("d"), None);
+
+    let keys = keys();
+    let search = |q: &str| keys.binary_search_by(|k| k.compare(Unflipped::from_ref(q)));
+    assert_eq!(search("a"), Ok(0));
+    assert_eq!(search("c"), Ok(2));
+    assert_eq!(search("bb"), Err(2));
+    assert_eq!(
+      String::from("a").compare(Unflipped::from_ref("b")),
+      Ordering::Less
+    );
+  }
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "derive")]
+
+extern crate equivalent_flipped;
+extern crate trybuild;
+
+use std::{
+  cmp::Ordering,
+  collections::hash_map::DefaultHasher,
+  hash::{Hash, Hasher},
+};
+
+use equivalent_flipped::{Comparable, Equivalent, EquivalentHash};
+
+#[derive(Equivalent, Comparable)]
+#[equivalent(query = UserRef<'_>, hash)]
+struct User {
+  #[equivalent(deref)]
+  name: String,
+  #[equivalent(rename = id)]
+  user_id: u64,
+  #[equivalent(skip)]
+  #[allow(dead_code)]
+  visits: u32,
+}
+
+#[derive(Hash)]
+struct UserRef<'a> {
+  name: &'a str,
+  id: u64,
+}
+
+#[derive(Equivalent, Comparable)]
+#[equivalent(query = PairRef<'_>)]
+struct Pair(
+  #[equivalent(rename = 1)] u32,
+  #[equivalent(rename = 0, deref)] String,
+);
+
+struct PairRef<'a>(&'a str, u32);
+
+#[derive(Equivalent, Comparable)]
+#[equivalent(query = Probe<u8>)]
+#[equivalent(query = "Named")]
+struct Tag {
+  value: u8,
+  #[equivalent(rename = "revision")]
+  version: u32,
+}
+
+struct Probe<T> {
+  value: T,
+  revision: u32,
+}
+
+struct Named {
+  value: u8,
+  revision: u32,
+}
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+  let mut state = DefaultHasher::new();
+  value.hash(&mut state);
+  state.finish()
+}
+
+fn hash_like<K: EquivalentHash<Q>, Q: Hash + ?Sized>(key: &K) -> u64 {
+  let mut state = DefaultHasher::new();
+  key.hash_like(&mut state);
+  state.finish()
+}
+
+fn user(name: &str, user_id: u64, visits: u32) -> User {
+  User {
+    name: name.into(),
+    user_id,
+    visits,
+  }
+}
+
+#[test]
+fn named_fields() {
+  let key = user("ann", 7, 3);
+  let query = UserRef { name: "ann", id: 7 };
+  assert!(key.equivalent(&query));
+  assert_eq!(key.compare(&query), Ordering::Equal);
+  // `visits` is skipped.
+  assert!(user("ann", 7, 100).equivalent(&query));
+
+  assert!(!key.equivalent(&UserRef { name: "ann", id: 8 }));
+  assert_eq!(key.compare(&UserRef { name: "ann", id: 8 }), Ordering::Less);
+  // Fields are compared in declaration order, `name` first.
+  assert_eq!(
+    key.compare(&UserRef { name: "amy", id: 9 }),
+    Ordering::Greater
+  );
+}
+
+#[test]
+fn hash_matches_query() {
+  let key = user("ann", 7, 3);
+  let query = UserRef { name: "ann", id: 7 };
+  assert_eq!(hash_like::<_, UserRef>(&key), hash(&query));
+  assert_eq!(hash_like::<_, UserRef>(&user("ann", 7, 100)), hash(&query));
+}
+
+#[test]
+fn tuple_fields() {
+  let key = Pair(1, "b".into());
+  assert!(key.equivalent(&PairRef("b", 1)));
+  assert!(!key.equivalent(&PairRef("b", 2)));
+  assert_eq!(key.compare(&PairRef("a", 1)), Ordering::Greater);
+  assert_eq!(key.compare(&PairRef("a", 2)), Ordering::Less);
+}
+
+#[test]
+fn repeated_queries() {
+  let key = Tag {
+    value: 3,
+    version: 1,
+  };
+  assert!(key.equivalent(&Probe {
+    value: 3,
+    revision: 1
+  }));
+  assert!(key.equivalent(&Named {
+    value: 3,
+    revision: 1
+  }));
+  assert_eq!(
+    key.compare(&Named {
+      value: 2,
+      revision: 5
+    }),
+    Ordering::Greater
+  );
+  assert_eq!(
+    key.compare(&Probe {
+      value: 3,
+      revision: 0
+    }),
+    Ordering::Greater
+  );
+}
+
+#[test]
+fn ui() {
+  let t = trybuild::TestCases::new();
+  t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,12 @@
+extern crate equivalent_flipped;
+
+use equivalent_flipped::Equivalent;
+
+#[derive(Equivalent)]
+#[equivalent(query = u32)]
+enum Either {
+  Left(u32),
+  Right(u32),
+}
+
+fn main() {}
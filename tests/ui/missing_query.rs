@@ -0,0 +1,10 @@
+extern crate equivalent_flipped;
+
+use equivalent_flipped::Comparable;
+
+#[derive(Comparable)]
+struct Key {
+  id: u32,
+}
+
+fn main() {}
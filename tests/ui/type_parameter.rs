@@ -0,0 +1,15 @@
+extern crate equivalent_flipped;
+
+use equivalent_flipped::Equivalent;
+
+#[derive(Equivalent)]
+#[equivalent(query = Probe<T>)]
+struct Versioned<T> {
+  value: T,
+}
+
+struct Probe<T> {
+  value: T,
+}
+
+fn main() {}
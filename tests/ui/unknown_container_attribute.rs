@@ -0,0 +1,15 @@
+extern crate equivalent_flipped;
+
+use equivalent_flipped::Equivalent;
+
+#[derive(Equivalent)]
+#[equivalent(query = KeyRef, ord)]
+struct Key {
+  id: u32,
+}
+
+struct KeyRef {
+  id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+extern crate equivalent_flipped;
+
+use equivalent_flipped::Equivalent;
+
+#[derive(Equivalent)]
+#[equivalent(query = KeyRef)]
+struct Key {
+  #[equivalent(flatten)]
+  id: u32,
+}
+
+struct KeyRef {
+  id: u32,
+}
+
+fn main() {}